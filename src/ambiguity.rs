@@ -0,0 +1,129 @@
+use crate::antenna::pattern;
+use crate::bistatic;
+use crate::config::{C, RadarConfig};
+use crate::sensitivity::to_db;
+
+const MAX_ORDER: i32 = 10;
+
+pub struct RangeAmbiguity {
+    pub order: i32, // Number of PRIs between the ambiguous and the main echo
+    pub ground_range: f64, // m
+    pub relative_power: f64, // dB, relative to the main echo
+}
+
+// Echo power of the ground point seen under `look_angle_deg`, up to constant factors:
+// transmit and receive elevation patterns over Rt Rr² times the ground gradient of
+// the range sum, which reduces to the usual R³ sin θi in a monostatic configuration.
+fn echo_power(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    let range = config.slant_range_at(look_angle_deg);
+    let rx_range = bistatic::receiver_range(config, look_angle_deg);
+    let tx_gain = pattern(look_angle_deg - config.look_angle, config.elevation_aperture_angle);
+    let rx_angle = bistatic::receiver_angle(config, look_angle_deg);
+    let rx_gain = pattern(rx_angle - config.receiver_look_angle(), config.elevation_aperture_angle);
+    tx_gain * rx_gain / (range * rx_range.powi(2) * bistatic::ground_range_gradient(config, look_angle_deg))
+}
+
+// Ground points whose echoes of earlier (order > 0) or later (order < 0) pulses
+// arrive together with the echo of the point seen under `look_angle_deg`, their
+// range sums being a multiple of c·PRI apart.
+pub fn range_ambiguities(config: &RadarConfig, look_angle_deg: f64) -> Vec<RangeAmbiguity> {
+    let range_sum = bistatic::range_sum(config, look_angle_deg);
+    let main = echo_power(config, look_angle_deg);
+    (-MAX_ORDER..=MAX_ORDER)
+        .filter(|&order| order != 0)
+        .filter_map(|order| {
+            let angle = bistatic::look_angle_at_range_sum(config, range_sum + order as f64 * C * config.pri * 1e-6);
+            if angle.is_nan() {
+                return None;
+            }
+            Some(RangeAmbiguity {
+                order,
+                ground_range: config.ground_range(angle),
+                relative_power: to_db(echo_power(config, angle) / main),
+            })
+        })
+        .collect()
+}
+
+// Range ambiguity to signal ratio at `look_angle_deg`, in dB.
+pub fn rasr(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    let ratio: f64 = range_ambiguities(config, look_angle_deg)
+        .iter()
+        .map(|a| 10f64.powf(a.relative_power / 10.0))
+        .sum();
+    to_db(ratio)
+}
+
+// Azimuth ambiguity to signal ratio, in dB. The two-way azimuth pattern is
+// expressed against the Doppler frequency and the processed band, the smaller of
+// the 3 dB Doppler bandwidth and the PRF, is compared with its aliases at
+// multiples of the PRF seen by each agility step.
+pub fn aasr(config: &RadarConfig) -> f64 {
+    let wavelength = config.wavelength();
+    let velocity = config.carrier_velocity;
+    let prf = config.final_prf();
+    let doppler = |angle_deg: f64| 2.0 * velocity * angle_deg.to_radians().sin() / wavelength;
+    let gain = |frequency: f64| {
+        let sin_angle = frequency * wavelength / (2.0 * velocity);
+        if sin_angle.abs() >= 1.0 {
+            return 0.0;
+        }
+        let g = pattern(sin_angle.asin().to_degrees(), config.azimuth_aperture_angle);
+        g * g
+    };
+
+    let band = (2.0 * doppler(config.azimuth_aperture_angle / 2.0)).min(prf);
+    let nb_steps = 200;
+    let integral = |shift: f64| -> f64 {
+        (0..nb_steps)
+            .map(|i| -band / 2.0 + band * (i as f64 + 0.5) / nb_steps as f64)
+            .map(|f| gain(f + shift))
+            .sum()
+    };
+    let ambiguous: f64 = (1..=MAX_ORDER)
+        .map(|m| integral(m as f64 * prf) + integral(-m as f64 * prf))
+        .sum();
+    to_db(ambiguous / integral(0.0))
+}
+
+// RASR across the swath, as `[ground range (m), RASR (dB)]` points.
+pub fn rasr_profile(config: &RadarConfig, nb_points: usize) -> Vec<[f64; 2]> {
+    config.swath_angles(nb_points)
+        .into_iter()
+        .map(|angle| [config.ground_range(angle), rasr(config, angle)])
+        .filter(|[_, rasr]| rasr.is_finite())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ambiguities_move_with_the_pri() {
+        let config = RadarConfig::default();
+        let ambiguities = range_ambiguities(&config, 45.0);
+        let next = ambiguities.iter().find(|a| a.order == 1).unwrap();
+        let range = (config.carrier_height.powi(2) + next.ground_range.powi(2)).sqrt();
+        assert!((range - config.slant_range() - C * 50e-6).abs() < 1e-6);
+        assert!(next.relative_power < 0.0);
+
+        // A higher PRF brings the ambiguities closer to the beam and raises the RASR.
+        let faster = RadarConfig { pri: 20.0, ..config.clone() };
+        assert!(rasr(&faster, 45.0) > rasr(&config, 45.0));
+    }
+
+    #[test]
+    fn aasr_drops_with_the_prf() {
+        let config = RadarConfig {
+            center_frequency: 9.6,
+            azimuth_aperture_angle: 3.0,
+            pri: 200.0,
+            ..RadarConfig::default()
+        };
+        let slow = RadarConfig { pri: 1000.0, ..config.clone() };
+        assert!(aasr(&config) < aasr(&slow));
+        assert!(aasr(&config) < -50.0);
+        assert!(aasr(&slow) > -50.0);
+    }
+}
@@ -0,0 +1,25 @@
+use std::f64::consts::PI;
+
+// One-way power pattern of a uniformly illuminated aperture, normalised to 1 on
+// boresight, for a direction `offset_deg` away from it and a 3 dB beamwidth of
+// `beamwidth_deg`.
+pub fn pattern(offset_deg: f64, beamwidth_deg: f64) -> f64 {
+    let x = 0.886 * offset_deg / beamwidth_deg;
+    if x == 0.0 {
+        return 1.0;
+    }
+    let sinc = (PI * x).sin() / (PI * x);
+    sinc * sinc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn half_power_at_half_beamwidth() {
+        assert_eq!(pattern(0.0, 10.0), 1.0);
+        assert!((pattern(5.0, 10.0) - 0.5).abs() < 1e-3);
+        assert!((pattern(-5.0, 10.0) - 0.5).abs() < 1e-3);
+    }
+}
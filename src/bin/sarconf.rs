@@ -0,0 +1,223 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use clap::{Parser, Subcommand, ValueEnum};
+use sarconf::pri::{self, PriMode};
+use sarconf::figure::{Figure, ImageFormat};
+use sarconf::{Format, RadarConfig, ambiguity, chronogram, geometry, resolution, sensitivity, timing};
+
+#[derive(Parser)]
+#[command(name = "sarconf", about = "Evaluate and convert SARConf configurations without the GUI")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the quantities derived from a configuration
+    Show { file: PathBuf },
+    /// Convert a configuration, the formats being given by the file extensions
+    Convert { input: PathBuf, output: PathBuf },
+    /// List the violated timing constraints, exiting with a non-zero status on any error
+    Check {
+        file: PathBuf,
+        /// Also exit with a non-zero status on any warning
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Save a plot of the GUI as PNG or SVG, the format being given by the output extension
+    Plot {
+        #[arg(value_enum)]
+        plot: PlotKind,
+        file: PathBuf,
+        output: PathBuf,
+        /// Displayed abscissas, instead of fitting the plot
+        #[arg(long, value_name = "MIN,MAX", value_parser = parse_range)]
+        x_range: Option<[f64; 2]>,
+        /// Displayed ordinates, instead of fitting the plot
+        #[arg(long, value_name = "MIN,MAX", value_parser = parse_range)]
+        y_range: Option<[f64; 2]>,
+        /// Image size in px
+        #[arg(long, value_name = "WIDTHxHEIGHT", value_parser = parse_size)]
+        size: Option<[u32; 2]>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PlotKind {
+    Geometry,
+    Chronogram,
+}
+
+fn parse_range(text: &str) -> Result<[f64; 2], String> {
+    let (min, max) = text.split_once(',').ok_or("expected MIN,MAX")?;
+    let range = [min.trim().parse::<f64>(), max.trim().parse::<f64>()];
+    match range {
+        [Ok(min), Ok(max)] if min < max => Ok([min, max]),
+        [Ok(_), Ok(_)] => Err("MIN must be below MAX".to_string()),
+        _ => Err("expected two numbers".to_string()),
+    }
+}
+
+fn parse_size(text: &str) -> Result<[u32; 2], String> {
+    let (width, height) = text.split_once('x').ok_or("expected WIDTHxHEIGHT")?;
+    match [width.trim().parse::<u32>(), height.trim().parse::<u32>()] {
+        [Ok(width), Ok(height)] if width > 0 && height > 0 => Ok([width, height]),
+        _ => Err("expected two sizes above 0".to_string()),
+    }
+}
+
+fn format_of(path: &Path) -> Result<Format, String> {
+    Format::from_path(path).ok_or_else(|| {
+        let extensions: Vec<_> = Format::ALL.iter().map(|f| format!(".{}", f.extension())).collect();
+        format!("{}: unknown format, expected one of {}", path.display(), extensions.join(", "))
+    })
+}
+
+fn load(path: &Path) -> Result<RadarConfig, String> {
+    let bytes = std::fs::read(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let (config, warnings) = format_of(path)?
+        .import(&bytes)
+        .map_err(|errors| format!("{}:\n  {}", path.display(), errors.join("\n  ")))?;
+    for warning in warnings {
+        eprintln!("warning: {}", warning);
+    }
+    Ok(config)
+}
+
+fn show(config: &RadarConfig) {
+    let (near, far) = config.ground_illumination();
+    let (start, end) = config.numerization_distances();
+    println!("Configuration:          {}", config.config_name);
+    println!("PRF:                    {:.1} Hz", config.prf());
+    if config.pri_mode != PriMode::Constant {
+        let sequence: Vec<String> = config.raw_pri_sequence().iter().map(|pri| format!("{:.3}", pri)).collect();
+        println!("PRI sequence:           {} ({}) µs", config.pri_mode.name(), sequence.join(", "));
+        println!("Mean PRF:               {:.1} Hz", config.mean_prf());
+    }
+    println!("Final PRF:              {:.1} Hz", config.final_prf());
+    let (swath_start, swath_end) = pri::swath_ranges(config);
+    if swath_end.is_finite() {
+        let mut blind: Vec<String> = pri::blind_ranges(config, swath_start, swath_end)
+            .into_iter()
+            .map(|(start, end)| format!("{:.0} m to {:.0} m", start, end))
+            .collect();
+        if blind.len() == pri::MAX_BLIND_RANGES {
+            blind.push("…".to_string());
+        }
+        println!("Blind ranges in swath:  {}", if blind.is_empty() { "none".to_string() } else { blind.join(", ") });
+    }
+    println!("Earth model:            {}", config.earth_model.name());
+    println!("Radar-Target distance:  {:.1} m", config.slant_range());
+    println!("Incidence angle:        {:.3}°", config.incidence_angle(config.look_angle));
+    println!("Ground illumination:    from {:.1} m to {:.1} m", near, far);
+    println!("Numerization distances: from {:.1} m to {:.1} m", start, end);
+    println!("Nadir echo:             {:.3} µs", config.nadir_echo_time());
+    let (first, last) = config.swath_echo_times();
+    println!("Swath echo:             from {:.3} µs to {:.3} µs", first, last);
+    if config.bsar_config {
+        println!("Direct path:            {:.3} µs", config.direct_path_time());
+    }
+    let slant_range = resolution::slant_range_resolution(config);
+    if slant_range.is_finite() {
+        println!("Slant range resolution: {:.3} m", slant_range);
+    }
+    let ground_range = resolution::ground_range_resolution_profile(config, 101);
+    if let (Some(first), Some(last)) = (ground_range.first(), ground_range.last()) {
+        println!("Ground resolution:      from {:.3} m to {:.3} m", first[1], last[1]);
+    }
+    // The coarsest of the agility steps, at the longest wavelength.
+    let azimuth = resolution::azimuth_resolution_profile(&config.wavelength_extremes().1, 101);
+    if let (Some(first), Some(last)) = (azimuth.first(), azimuth.last()) {
+        println!("Azimuth resolution:     from {:.3} m to {:.3} m", first[1], last[1]);
+    }
+    if config.nb_channels > 1 {
+        for (i, channel) in config.channels.iter().enumerate() {
+            let [along, across, up] = channel.position;
+            println!(
+                "{:<24}{}, phase centre ({:.3}, {:.3}, {:.3}) m, cable delay {:.1} ns, gain {:.1} dB",
+                format!("Channel {}:", i),
+                if channel.enabled { "enabled" } else { "disabled" },
+                along, across, up, channel.cable_delay, channel.gain
+            );
+        }
+    }
+    let steps = config.agility_steps();
+    if steps.len() > 1 {
+        let frequencies: Vec<String> = steps.iter().map(|s| format!("{:.4}", s.center_frequency)).collect();
+        let sequence: Vec<String> = config.agility_sequence().iter().map(|s| format!("F{}", s)).collect();
+        println!("Agility frequencies:    {} GHz", frequencies.join(", "));
+        println!("Agility sequence:       {}", sequence.join(" "));
+    }
+    let nesz: Vec<f64> = steps.iter()
+        .flat_map(|step| sensitivity::nesz_profile(step, 101))
+        .map(|p| p[1])
+        .collect();
+    if !nesz.is_empty() {
+        let min = nesz.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = nesz.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        println!("NESZ:                   from {:.1} dB to {:.1} dB", min, max);
+    }
+    if let Some(worst) = ambiguity::rasr_profile(config, 101).iter().map(|p| p[1]).reduce(f64::max) {
+        println!("RASR:                   up to {:.1} dB", worst);
+    }
+    let aasr = steps.iter().map(ambiguity::aasr).fold(f64::NAN, f64::max);
+    if aasr.is_finite() {
+        println!("AASR:                   {:.1} dB", aasr);
+    }
+}
+
+fn run(command: Command) -> Result<ExitCode, String> {
+    match command {
+        Command::Show { file } => {
+            show(&load(&file)?);
+        }
+        Command::Convert { input, output } => {
+            let config = load(&input)?;
+            let bytes = format_of(&output)?.export(&config)?;
+            std::fs::write(&output, bytes).map_err(|err| format!("{}: {}", output.display(), err))?;
+        }
+        Command::Check { file, strict } => {
+            let violations = timing::violations(&load(&file)?);
+            for violation in &violations {
+                println!("{}", violation);
+            }
+            let failure = if strict { timing::Severity::Warning } else { timing::Severity::Error };
+            if violations.iter().any(|v| v.severity >= failure) {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Plot { plot, file, output, x_range, y_range, size } => {
+            let config = load(&file)?;
+            let format = ImageFormat::from_path(&output)
+                .ok_or_else(|| format!("{}: unknown image format, expected .png or .svg", output.display()))?;
+            let mut figure: Figure = match plot {
+                PlotKind::Geometry => geometry::figure(&config),
+                PlotKind::Chronogram => chronogram::figure(&config),
+            };
+            if let Some(size) = size {
+                figure.size = size;
+            }
+            let mut bounds = figure.bounds();
+            if let Some([min, max]) = x_range {
+                (bounds[0][0], bounds[1][0]) = (min, max);
+            }
+            if let Some([min, max]) = y_range {
+                (bounds[0][1], bounds[1][1]) = (min, max);
+            }
+            let bytes = format.render(&figure, bounds)?;
+            std::fs::write(&output, bytes).map_err(|err| format!("{}: {}", output.display(), err))?;
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,141 @@
+// Bistatic geometry. Positions are expressed in a frame centred on the nadir of
+// the transmitter, x across track towards the swath, y along track and z up. The
+// receiver flies at `rx_carrier_height`, offset by the along and across track
+// baselines; the curvature of the earth between the two nadirs is neglected.
+// In a monostatic configuration the receiver is the transmitter.
+use crate::config::RadarConfig;
+use crate::earth;
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+fn unit(a: [f64; 3]) -> [f64; 3] {
+    let n = norm(a);
+    [a[0] / n, a[1] / n, a[2] / n]
+}
+
+pub fn transmitter(config: &RadarConfig) -> [f64; 3] {
+    [0.0, 0.0, config.carrier_height]
+}
+
+pub fn receiver(config: &RadarConfig) -> [f64; 3] {
+    if config.bsar_config {
+        [config.rx_across_track_baseline, config.rx_along_track_baseline, config.rx_carrier_height]
+    } else {
+        transmitter(config)
+    }
+}
+
+// Ground point seen by the transmitter under `look_angle_deg`.
+pub fn ground_point(config: &RadarConfig, look_angle_deg: f64) -> [f64; 3] {
+    let [x, z] = earth::surface_point(config.earth_radius(), config.carrier_height, look_angle_deg);
+    [x, 0.0, z]
+}
+
+// Distance from the receiver to the ground point seen by the transmitter under `look_angle_deg`, in m.
+pub fn receiver_range(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    norm(sub(ground_point(config, look_angle_deg), receiver(config)))
+}
+
+// Angle from the receiver nadir under which the receiver sees that ground point, in degrees.
+pub fn receiver_angle(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    let los = sub(ground_point(config, look_angle_deg), receiver(config));
+    (-los[2] / norm(los)).acos().to_degrees()
+}
+
+// Transmitter to ground to receiver path length, in m.
+pub fn range_sum(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    config.slant_range_at(look_angle_deg) + receiver_range(config, look_angle_deg)
+}
+
+// Transmitter to receiver path length, in m.
+pub fn direct_path(config: &RadarConfig) -> f64 {
+    norm(sub(receiver(config), transmitter(config)))
+}
+
+// Transmitter look angle of the ground point whose echo travels `range_sum_m`,
+// NaN when no visible ground point does.
+pub fn look_angle_at_range_sum(config: &RadarConfig, range_sum_m: f64) -> f64 {
+    if !config.bsar_config {
+        return config.look_angle_at_range(range_sum_m / 2.0);
+    }
+    let horizon = earth::horizon(config.earth_radius(), config.carrier_height);
+    let (mut low, mut high) = (0.0, horizon - 1e-6);
+    if !(range_sum(config, low)..=range_sum(config, high)).contains(&range_sum_m) {
+        return f64::NAN;
+    }
+    for _ in 0..100 {
+        let mid = 0.5 * (low + high);
+        if range_sum(config, mid) < range_sum_m {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    0.5 * (low + high)
+}
+
+// Range from the transmitter to the ground along the direction of depression
+// `angle_deg` below horizontal, such that the path through the receiver is
+// `range_sum_m`. The locus is the ellipse whose foci are the two carriers.
+pub fn iso_range(config: &RadarConfig, range_sum_m: f64, angle_deg: f64) -> f64 {
+    let (sin, cos) = angle_deg.to_radians().sin_cos();
+    let d = sub(transmitter(config), receiver(config));
+    let d2 = dot(d, d);
+    (range_sum_m * range_sum_m - d2) / (2.0 * (range_sum_m + dot(d, [cos, 0.0, -sin])))
+}
+
+// Norm of the gradient of the range sum along the ground at the point seen under
+// `look_angle_deg`: 2 sin θi in a monostatic configuration. The ground range
+// resolution is c / (B times this factor).
+pub fn ground_range_gradient(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    let point = ground_point(config, look_angle_deg);
+    let radius = config.earth_radius();
+    let normal = if radius.is_infinite() { [0.0, 0.0, 1.0] } else { unit(sub(point, [0.0, 0.0, -radius])) };
+    let tx = unit(sub(point, transmitter(config)));
+    let rx = unit(sub(point, receiver(config)));
+    let gradient = [tx[0] + rx[0], tx[1] + rx[1], tx[2] + rx[2]];
+    let along_normal = dot(gradient, normal);
+    norm(sub(gradient, [along_normal * normal[0], along_normal * normal[1], along_normal * normal[2]]))
+}
+
+// Rate at which the bistatic angle to the point seen under `look_angle_deg`
+// sweeps during the integration, in rad/s: 2 v / R in a monostatic configuration.
+// The azimuth resolution is λ / (Tint times this rate).
+pub fn angular_rate(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    config.carrier_velocity / config.slant_range_at(look_angle_deg)
+        + config.receiver_velocity() / receiver_range(config, look_angle_deg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monostatic_and_bistatic_range_sums() {
+        let config = RadarConfig::default();
+        assert!((range_sum(&config, 45.0) - 2.0 * config.slant_range()).abs() < 1e-9);
+        assert!((ground_range_gradient(&config, 45.0) - 2.0 * 45f64.to_radians().sin()).abs() < 1e-9);
+
+        let config = RadarConfig {
+            bsar_config: true,
+            rx_carrier_height: 2000.0,
+            rx_across_track_baseline: -500.0,
+            rx_along_track_baseline: 300.0,
+            ..config
+        };
+        let sum = range_sum(&config, 50.0);
+        assert!((look_angle_at_range_sum(&config, sum) - 50.0).abs() < 1e-6);
+        assert!((iso_range(&config, sum, 40.0) - config.slant_range_at(50.0)).abs() < 1e-6);
+        assert!(look_angle_at_range_sum(&config, direct_path(&config)).is_nan());
+    }
+}
@@ -1,13 +1,21 @@
-use eframe::egui::{Color32, Ui, Vec2b};
-use egui_plot::{Plot, Line, Legend, Corner};
+// Timing of the windows repeated after each transmitted pulse.
+use crate::figure::{self, Figure, Rgb, Series, Style};
+use crate::{RadarConfig, genio, pri, timing};
+
+// Pulses drawn at most, however late the echoes come back.
+const MAX_PULSES: usize = 256;
 
 pub struct Window {
     pub name: String,
     pub start_time: f64,
     pub duration: f64,
     pub height: f64,
+    pub base: f64, // Bottom of the window, to draw it in its own lane
     pub dashed: bool,
-    pub color: Option<Color32>,
+    pub color: Rgb,
+    pub highlighted: bool, // Involved in a timing violation
+    pub overlay: bool, // Drawn over other windows, which it is expected to overlap
+    pub cycle: Option<(usize, usize)>, // Drawn only in the PRIs i for which i % .1 == .0
 }
 
 impl Default for Window {
@@ -17,8 +25,12 @@ impl Default for Window {
             start_time: 0.0,
             duration: 1.0,
             height: 1.0,
+            base: 0.0,
             dashed: false,
-            color: None,
+            color: figure::GRAY,
+            highlighted: false,
+            overlay: false,
+            cycle: None,
         }
     }
 }
@@ -31,59 +43,304 @@ impl Window {
     pub fn end(&self) -> f64 {
         self.start_time + self.duration
     }
+
+    // Tells whether the window is drawn after pulse `pulse`.
+    fn drawn_in(&self, pulse: usize) -> bool {
+        self.cycle.is_none_or(|(position, length)| pulse % length == position)
+    }
+}
+
+// Intersection of a repetition of `first` with a repetition of `second`.
+pub struct Overlap {
+    pub first: usize, // Index of the window
+    pub second: usize,
+    pub start: f64, // µs from the first pulse
+    pub end: f64,
+}
+
+// Every pair of drawn window repetitions intersecting when pulses start at
+// `pulse_times`, including a window meeting its own later repetitions. The
+// repetitions are swept in order of start time, each compared only with those
+// still running when it starts.
+pub fn overlaps(pulse_times: &[f64], windows: &[Window]) -> Vec<Overlap> {
+    // `(start, end, window)` of every drawn repetition.
+    let mut repetitions: Vec<(f64, f64, usize)> = Vec::new();
+    for (index, window) in windows.iter().enumerate().filter(|(_, w)| w.duration > 0.0) {
+        for (_, time) in pulse_times.iter().enumerate().filter(|(i, _)| window.drawn_in(*i)) {
+            repetitions.push((time + window.start(), time + window.end(), index));
+        }
+    }
+    repetitions.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut overlaps = Vec::new();
+    let mut running: Vec<(f64, f64, usize)> = Vec::new();
+    for (start, end, index) in repetitions {
+        running.retain(|r| r.1 > start);
+        for &(_, other_end, other) in &running {
+            overlaps.push(Overlap { first: other.min(index), second: other.max(index), start, end: end.min(other_end) });
+        }
+        running.push((start, end, index));
+    }
+    overlaps
 }
 
-pub fn plot(ui: &mut Ui, pri: f64, windows: Vec<Window>) {
-    let mut nb_of_ambiguities = 1;
+// Windows of the configuration as the digitiser is programmed, those involved
+// in a timing violation highlighted.
+pub fn windows(config: &RadarConfig) -> Vec<Window> {
+    let config = &genio::programmed(config);
+    let swath_echo = config.swath_echo_times();
+    let mut windows = vec![
+        Window {
+            name: timing::TX.to_string(),
+            start_time: config.tx_offset,
+            duration: config.tx_duration,
+            height: 1.0,
+            dashed: false,
+            color: figure::RED,
+            ..Default::default()
+        },
+        Window {
+            name: timing::NADIR.to_string(),
+            start_time: config.nadir_echo_time(),
+            duration: config.tx_duration,
+            height: 0.2,
+            dashed: true,
+            overlay: true,
+            color: figure::WHITE,
+            ..Default::default()
+        },
+        Window {
+            name: timing::SWATH_ECHO.to_string(),
+            start_time: swath_echo.0,
+            duration: swath_echo.1 - swath_echo.0 + config.tx_duration,
+            height: 0.4,
+            dashed: true,
+            overlay: true,
+            color: figure::LIGHT_GREEN,
+            ..Default::default()
+        },
+        Window {
+            name: timing::RX.to_string(),
+            start_time: config.rx_offset,
+            duration: config.rx_duration,
+            height: 1.0,
+            dashed: false,
+            color: figure::LIGHT_YELLOW,
+            ..Default::default()
+        },
+        Window {
+            name: timing::NOISE.to_string(),
+            start_time: config.rx_noise_offset,
+            duration: config.rx_noise_duration,
+            height: 0.8,
+            dashed: false,
+            color: figure::GOLD,
+            ..Default::default()
+        },
+        Window {
+            name: timing::REINJ.to_string(),
+            start_time: config.rx_reinj_offset,
+            duration: config.rx_reinj_duration,
+            height: 0.8,
+            dashed: false,
+            color: figure::GOLD,
+            ..Default::default()
+        },
+    ];
+    if config.full_resolution_rx_duration() > 0.0 {
+        windows.push(
+            Window {
+                name: timing::RX_FULL_RESOLUTION.to_string(),
+                start_time: config.rx_offset,
+                duration: config.full_resolution_rx_duration(),
+                height: 1.0,
+                dashed: true,
+                overlay: true,
+                color: figure::YELLOW,
+                ..Default::default()
+            },
+        );
+    }
+    if config.bsar_config {
+        windows.push(
+            Window {
+                name: timing::DIRECT_PATH.to_string(),
+                start_time: config.direct_path_time(),
+                duration: config.tx_duration,
+                height: 0.2,
+                dashed: true,
+                overlay: true,
+                color: figure::LIGHT_RED,
+                ..Default::default()
+            },
+        );
+    }
+    // One lane per agility step, showing the pulses transmitted at its frequency.
+    if config.nb_agilities > 1 {
+        let sequence = config.agility_sequence();
+        for (position, &step) in sequence.iter().enumerate() {
+            windows.push(
+                Window {
+                    name: format!("F{} ({:.4} GHz)", step, config.agility_frequency(step)),
+                    start_time: config.tx_offset,
+                    duration: config.tx_duration,
+                    height: 0.1,
+                    base: 1.1 + 0.15 * step as f64,
+                    color: [255, 100 + (155 * step / config.nb_agilities) as u8, 100],
+                    overlay: true,
+                    cycle: Some((position, sequence.len())),
+                    ..Default::default()
+                },
+            );
+        }
+    }
+    // One lane per enabled receiver channel, showing its RX window referred
+    // to the reference phase centre, above the agility lanes.
+    if config.nb_channels > 1 {
+        let nb_lanes = if config.nb_agilities > 1 { config.nb_agilities } else { 0 };
+        for (i, channel) in config.channels.iter().enumerate() {
+            if !channel.enabled {
+                continue;
+            }
+            let (start_time, duration) = config.channel_rx_window(i);
+            windows.push(
+                Window {
+                    name: format!("RX channel {}", i),
+                    start_time,
+                    duration,
+                    height: 0.1,
+                    base: 1.1 + 0.15 * (nb_lanes as usize + i) as f64,
+                    color: [100, 200, 100 + (155 * i / config.channels.len()) as u8],
+                    overlay: true,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+    // Echoes from beyond the horizon never come back.
+    windows.retain(|w| w.start().is_finite() && w.duration.is_finite());
+    let violations = timing::violations(config);
+    for window in &mut windows {
+        window.highlighted = violations.iter().any(|v| v.windows.contains(&window.name.as_str()));
+    }
+    windows
+}
+
+// Windows drawn after each pulse of the PRI sequence, repeated until every
+// window and every cycle of windows is shown at least once, up to `MAX_PULSES`
+// pulses, with their overlaps.
+pub fn figure(config: &RadarConfig) -> Figure {
+    let sequence = genio::programmed(config).pri_sequence();
+    let windows = windows(config);
+    let mean_pri = sequence.iter().sum::<f64>() / sequence.len() as f64;
+    let mut nb_of_ambiguities = sequence.len();
     for window in &windows {
         let end_time = window.end();
-        if end_time > nb_of_ambiguities as f64 * pri {
-            nb_of_ambiguities = (end_time / pri).ceil() as usize + 1;
+        if end_time > nb_of_ambiguities as f64 * mean_pri {
+            nb_of_ambiguities = ((end_time / mean_pri).ceil() as usize).saturating_add(1);
+        }
+        if let Some((_, length)) = window.cycle {
+            nb_of_ambiguities = nb_of_ambiguities.max(length);
         }
     }
+    let nb_of_ambiguities = nb_of_ambiguities.min(MAX_PULSES);
+    let pulse_times = pri::pulse_times(&sequence, nb_of_ambiguities + 1);
+    let end_time = pulse_times[nb_of_ambiguities];
+    let pulse_times = &pulse_times[..nb_of_ambiguities];
+    let top = windows.iter().map(|w| w.base + w.height).fold(1.0, f64::max) + 0.1;
+
+    let mut series: Vec<Series> = overlaps(pulse_times, &windows)
+        .into_iter()
+        .filter(|o| !windows[o.first].overlay && !windows[o.second].overlay)
+        .map(|o| Series {
+            name: "Overlap".to_string(),
+            points: vec![[o.start, 0.0], [o.start, 1.05], [o.end, 1.05], [o.end, 0.0]],
+            color: figure::RED,
+            fill: Some((0.0, 0.3)),
+            ..Default::default()
+        })
+        .collect();
 
-    let legend = Legend::default().position(Corner::RightBottom);
-
-    Plot::new("Chronogram")
-        .height(100.0)
-        .show_y(false)
-        .allow_boxed_zoom(false)
-        .allow_drag(Vec2b::new(true, false))
-        .allow_zoom(Vec2b::new(true, false))
-        .allow_scroll(Vec2b::new(true, false))
-        .show_axes(Vec2b::new(true, false))
-        .show_grid(Vec2b::new(true, false))
-        .default_y_bounds(0.0, 1.1)
-        .x_axis_formatter(|x, _| format!("{:.1} µs", x.value))
-        .include_x(nb_of_ambiguities as f64 * pri)
-        .legend(legend)
-        .show(ui, |plot_ui| {
-            for window in windows {
-                for i in 0..nb_of_ambiguities {
-                    let mut w = Line::new(
-                            if i > 0 {
-                                format!("{} (Ambiguity {})", window.name, i)
-                            } else {
-                                window.name.clone()
-                            },
-                            vec![
-                                [pri * i as f64 + window.start(), 0.0],
-                                [pri * i as f64 + window.start(), window.height],
-                                [pri * i as f64 + window.end(), window.height],
-                                [pri * i as f64 + window.end(), 0.0],
-                            ],
-                        ).width(2.0).fill(0.0).fill_alpha(0.6 / (i as f32 + 1.0));
-
-                    if window.dashed {
-                        w = w.style(egui_plot::LineStyle::Dashed { length: 5.0 });
-                    }
-
-                    if let Some(c) = window.color {
-                        w = w.color(c);
-                    }
-
-                    plot_ui.line(w);
-                }
+    for window in &windows {
+        for (i, pulse_time) in pulse_times.iter().enumerate() {
+            if !window.drawn_in(i) {
+                continue;
             }
-        });
-}
\ No newline at end of file
+            series.push(Series {
+                name: if i > 0 && window.cycle.is_none() {
+                    format!("{} (Ambiguity {})", window.name, i)
+                } else {
+                    window.name.clone()
+                },
+                points: vec![
+                    [pulse_time + window.start(), window.base],
+                    [pulse_time + window.start(), window.base + window.height],
+                    [pulse_time + window.end(), window.base + window.height],
+                    [pulse_time + window.end(), window.base],
+                ],
+                color: window.color,
+                width: 2.0,
+                style: if window.dashed { Style::Dashed } else { Style::Solid },
+                fill: Some((window.base, 0.6 / (i as f32 + 1.0))),
+                highlighted: window.highlighted,
+                ..Default::default()
+            });
+        }
+    }
+
+    Figure {
+        title: format!("{} - Chronogram", config.config_name),
+        x_unit: "µs",
+        show_y: false,
+        x_include: vec![end_time],
+        y_bounds: Some([0.0, top]),
+        size: [1600, (250.0 * top / 1.1) as u32],
+        series,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earth::EarthModel;
+
+    #[test]
+    fn overlaps_across_repetitions() {
+        let windows = [
+            Window { name: "TX".to_string(), start_time: 0.0, duration: 10.0, ..Default::default() },
+            Window { name: "RX".to_string(), start_time: 20.0, duration: 85.0, ..Default::default() },
+        ];
+        let overlaps = overlaps(&[0.0, 100.0], &windows);
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!((overlaps[0].first, overlaps[0].second), (0, 1));
+        assert_eq!((overlaps[0].start, overlaps[0].end), (100.0, 105.0));
+
+        // A window longer than the PRI meets its own next repetition.
+        let overlaps = super::overlaps(&[0.0, 50.0], &windows[1..]);
+        assert!(overlaps.iter().any(|o| o.start == 70.0 && o.end == 105.0));
+
+        // Staggered pulses move the overlap.
+        let overlaps = super::overlaps(&[0.0, 110.0], &windows);
+        assert!(overlaps.is_empty());
+
+        // Each RX window meets the next pulse only.
+        let pulse_times = pri::pulse_times(&[100.0], crate::config::MAX_PRI_STEPS as usize);
+        let overlaps = super::overlaps(&pulse_times, &windows);
+        assert_eq!(overlaps.len(), pulse_times.len() - 1);
+        assert!(overlaps.iter().all(|o| o.end - o.start == 5.0));
+    }
+
+    #[test]
+    fn grazing_look_angle() {
+        for earth_model in [EarthModel::Flat, EarthModel::Spherical] {
+            for look_angle in [81.0, 85.0, 90.0] {
+                let config = RadarConfig { look_angle, earth_model, ..RadarConfig::default() };
+                let (first, last) = config.swath_echo_times();
+                assert!(first.is_finite() && last.is_finite() && last > first, "{:?} at {}°", earth_model, look_angle);
+                // The echo spans hundreds of PRIs, of which the chronogram draws at most `MAX_PULSES`.
+                assert!(figure(&config).bounds()[1][0] <= 1.05 * MAX_PULSES as f64 * config.pri);
+            }
+        }
+    }
+}
@@ -0,0 +1,465 @@
+use crate::bistatic;
+use crate::earth::{self, EarthModel};
+use crate::pri::{self, PriMode};
+
+pub const C: f64 = 299792458.0; // Speed of light in m/s
+pub const MAX_AGILITIES: u32 = 64; // Agility steps the frequency synthesiser can cycle through
+pub const MAX_CHANNELS: u32 = 64; // Receiver channels of the acquisition system
+pub const MIN_PRI: f64 = 1.0; // µs
+pub const MAX_PRI_STEPS: u32 = 256; // PRIs of a staggered, jittered or listed sequence
+
+// Receiver channel, with its own antenna and acquisition chain.
+#[derive(Clone, PartialEq)]
+pub struct Channel {
+    pub position: [f64; 3], // m, phase centre along track, across track (towards the swath) and up from the reference
+    pub cable_delay: f64, // ns
+    pub gain: f64, // dB, added to the RX gain
+    pub enabled: bool,
+}
+
+impl Default for Channel {
+    fn default() -> Self {
+        Self {
+            position: [0.0; 3],
+            cable_delay: 0.0,
+            gain: 0.0,
+            enabled: true,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct RadarConfig {
+    pub config_name: String,
+    pub bsar_config: bool,
+
+    // Antenna parameters
+    pub elevation_aperture_angle: f64, // deg
+    pub azimuth_aperture_angle: f64, // deg
+
+    // Geometry parameters
+    pub carrier_velocity: f64, // m/s
+    pub carrier_height: f64, // m
+    pub look_angle: f64, // deg
+    pub earth_model: EarthModel,
+    pub latitude: f64, // deg
+
+    // Receiver carrier parameters, used in bistatic configuration
+    pub rx_carrier_height: f64, // m
+    pub rx_carrier_velocity: f64, // m/s
+    pub rx_look_angle: f64, // deg
+    pub rx_along_track_baseline: f64, // m
+    pub rx_across_track_baseline: f64, // m
+
+    // Transmission parameters
+    pub pri: f64, // µs
+    pub pri_mode: PriMode,
+    pub pri_steps: u32, // Number of PRIs in a staggered or jittered sequence
+    pub pri_stagger: f64, // µs between consecutive PRIs of a staggered sequence
+    pub pri_jitter: f64, // µs, largest deviation from the PRI in a jittered sequence
+    pub pri_list: Vec<f64>, // µs, PRI sequence given by the user
+    pub tx_offset: f64, // µs
+    pub tx_duration: f64, // µs
+    pub nb_agilities: u32,
+    pub agility_offsets: Vec<f64>, // MHz from the center frequency, one per agility step
+    pub agility_sequence: Vec<u32>, // Agility steps in transmission order, in increasing order when empty
+
+    // Receiver parameters
+    pub nb_channels: u32,
+    pub channels: Vec<Channel>, // One per receiver channel
+    pub fech: f64, // MHz
+    pub rx_offset: f64, // µs
+    pub rx_duration: f64, // µs
+    pub rx_noise_offset: f64, // µs
+    pub rx_noise_duration: f64, // µs
+    pub rx_reinj_offset: f64, // µs
+    pub rx_reinj_duration: f64, // µs
+    pub rx_margin: f64, // µs, kept on each side of the swath echo when fitting the RX window
+
+    // SAR sensitivity parameters
+    pub peak_power: f64, // W
+    pub loss_power: f64, // dB
+    pub gain_antenna: f64, // dB
+    pub noise_factor: f64, // dB
+    pub center_frequency: f64, // GHz
+    pub bandwidth: f64, // MHz
+
+    // Processing parameters
+    pub integration_length: f64, // m, 0 for the whole time in the azimuth beam
+
+    // Level parameters
+    pub retrodiff: f64, // dB
+    pub rx_gain: f64, // dB
+    pub adc_full_scale: f64, // dBm
+    pub adc_noise_floor: f64, // dBm
+
+    // Interference parameters
+    pub height_ambiguity: f64, // m
+    pub accuracy_height_ambiguity: f64, // m
+    pub perpendicular_baseline: f64, // m
+    pub phase_noise: f64, // deg
+}
+
+impl Default for RadarConfig {
+    fn default() -> Self {
+        Self {
+            config_name: String::from("Untitled"),
+            bsar_config: false,
+            elevation_aperture_angle: 18.0,
+            azimuth_aperture_angle: 0.0,
+            carrier_velocity: 120.0,
+            carrier_height: 3000.0,
+            look_angle: 45.0,
+            earth_model: EarthModel::Flat,
+            latitude: 45.0,
+            rx_carrier_height: 3000.0,
+            rx_carrier_velocity: 120.0,
+            rx_look_angle: 45.0,
+            rx_along_track_baseline: 0.0,
+            rx_across_track_baseline: 0.0,
+            pri: 100.0,
+            pri_mode: PriMode::Constant,
+            pri_steps: 3,
+            pri_stagger: 0.0,
+            pri_jitter: 0.0,
+            pri_list: vec![100.0],
+            tx_offset: 0.0,
+            tx_duration: 10.0,
+            nb_agilities: 1,
+            agility_offsets: vec![0.0],
+            agility_sequence: Vec::new(),
+            nb_channels: 1,
+            channels: vec![Channel::default()],
+            fech: 0.0,
+            rx_offset: 24.0,
+            rx_duration: 21.0,
+            rx_noise_offset: 15.0,
+            rx_noise_duration: 3.0,
+            rx_reinj_offset: 20.0,
+            rx_reinj_duration: 3.0,
+            rx_margin: 0.0,
+            peak_power: 0.0,
+            loss_power: 0.0,
+            gain_antenna: 0.0,
+            noise_factor: 0.0,
+            center_frequency: 0.0,
+            bandwidth: 0.0,
+            integration_length: 0.0,
+            retrodiff: 0.0,
+            rx_gain: 0.0,
+            adc_full_scale: 4.0,
+            adc_noise_floor: -70.0,
+            height_ambiguity: 0.0,
+            accuracy_height_ambiguity: 0.0,
+            perpendicular_baseline: 0.0,
+            phase_noise: 0.0,
+        }
+    }
+}
+
+impl RadarConfig {
+    // Pulse repetition frequency in Hz.
+    pub fn prf(&self) -> f64 {
+        1e6 / self.pri
+    }
+
+    // PRIs of the sequence as configured, in µs. A staggered sequence steps around
+    // the PRI so that its mean stays the PRI.
+    pub fn raw_pri_sequence(&self) -> Vec<f64> {
+        let n = self.pri_steps.max(1) as usize;
+        match self.pri_mode {
+            PriMode::Constant => vec![self.pri],
+            PriMode::Staggered => (0..n)
+                .map(|k| self.pri + (k as f64 - (n - 1) as f64 / 2.0) * self.pri_stagger)
+                .collect(),
+            PriMode::Jittered => (0..n).map(|k| self.pri + self.pri_jitter * pri::jitter(k)).collect(),
+            PriMode::List if self.pri_list.is_empty() => vec![self.pri],
+            PriMode::List => self.pri_list.clone(),
+        }
+    }
+
+    // PRIs transmitted in turn, in µs, each kept above `MIN_PRI` for the pulses to
+    // follow each other. A PRI shorter than the pulse is kept, the timing rules
+    // reporting it.
+    pub fn pri_sequence(&self) -> Vec<f64> {
+        self.raw_pri_sequence().into_iter().map(|p| p.max(MIN_PRI)).collect()
+    }
+
+    // Mean PRI of the sequence, in µs.
+    pub fn mean_pri(&self) -> f64 {
+        let sequence = self.pri_sequence();
+        sequence.iter().sum::<f64>() / sequence.len() as f64
+    }
+
+    // Mean pulse repetition frequency of the sequence, in Hz.
+    pub fn mean_prf(&self) -> f64 {
+        1e6 / self.mean_pri()
+    }
+
+    // PRF seen by each agility step, in Hz.
+    pub fn final_prf(&self) -> f64 {
+        self.mean_prf() / self.nb_agilities as f64
+    }
+
+    // Edges of the elevation beam, in degrees from nadir.
+    pub fn aperture_angles(&self) -> (f64, f64) {
+        (
+            self.look_angle - self.elevation_aperture_angle / 2.0,
+            self.look_angle + self.elevation_aperture_angle / 2.0,
+        )
+    }
+
+    // Radius of the ground surface, infinite for a flat earth, in m.
+    pub fn earth_radius(&self) -> f64 {
+        self.earth_model.radius(self.latitude)
+    }
+
+    // Distance from the radar to the ground along the look direction, in m.
+    pub fn slant_range(&self) -> f64 {
+        self.slant_range_at(self.look_angle)
+    }
+
+    // Distance from the radar to the ground seen under `angle_deg`, in m.
+    pub fn slant_range_at(&self, angle_deg: f64) -> f64 {
+        earth::slant_range(self.earth_radius(), self.carrier_height, angle_deg)
+    }
+
+    // Ground distance from nadir seen under `angle_deg`, in m.
+    pub fn ground_range(&self, angle_deg: f64) -> f64 {
+        earth::ground_range(self.earth_radius(), self.carrier_height, angle_deg)
+    }
+
+    // Incidence angle on the ground seen under `angle_deg`, in degrees.
+    pub fn incidence_angle(&self, angle_deg: f64) -> f64 {
+        earth::incidence_angle(self.earth_radius(), self.carrier_height, angle_deg)
+    }
+
+    // Look angle under which the ground is seen at slant range `range`, in degrees.
+    pub fn look_angle_at_range(&self, range: f64) -> f64 {
+        earth::look_angle(self.earth_radius(), self.carrier_height, range)
+    }
+
+    // Near and far ground ranges covered by the elevation beam, in m.
+    pub fn ground_illumination(&self) -> (f64, f64) {
+        let (min_angle, max_angle) = self.aperture_angles();
+        (self.ground_range(min_angle), self.ground_range(max_angle))
+    }
+
+    // Part of the RX window during which the whole echo of the pulse is received, in µs.
+    pub fn full_resolution_rx_duration(&self) -> f64 {
+        self.rx_duration - self.tx_offset - self.tx_duration
+    }
+
+    // Slant ranges at the start and at the end of the full resolution RX window, in m.
+    pub fn numerization_distances(&self) -> (f64, f64) {
+        (
+            0.5e-6 * C * self.rx_offset,
+            0.5e-6 * C * (self.rx_offset + self.full_resolution_rx_duration()),
+        )
+    }
+
+    // Transmitter to ground to receiver path lengths at the start and at the end
+    // of the full resolution RX window, in m: twice the numerization distances.
+    pub fn numerization_range_sums(&self) -> (f64, f64) {
+        let (start, end) = self.numerization_distances();
+        (2.0 * start, 2.0 * end)
+    }
+
+    // Carrier frequency of agility step `step`, in GHz.
+    pub fn agility_frequency(&self, step: u32) -> f64 {
+        self.center_frequency + self.agility_offsets.get(step as usize).copied().unwrap_or(0.0) / 1e3
+    }
+
+    // Agility steps in transmission order, the pulse k using step `sequence[k % len]`.
+    pub fn agility_sequence(&self) -> Vec<u32> {
+        if self.agility_sequence.is_empty() {
+            (0..self.nb_agilities).collect()
+        } else {
+            self.agility_sequence.clone()
+        }
+    }
+
+    // Configuration seen by each agility step, transmitting at its own frequency.
+    pub fn agility_steps(&self) -> Vec<RadarConfig> {
+        (0..self.nb_agilities)
+            .map(|step| RadarConfig { center_frequency: self.agility_frequency(step), ..self.clone() })
+            .collect()
+    }
+
+    // Agility steps of the shortest and longest wavelengths, which bound the
+    // quantities growing with the wavelength.
+    pub fn wavelength_extremes(&self) -> (RadarConfig, RadarConfig) {
+        let steps = self.agility_steps();
+        let by_frequency = |a: &&RadarConfig, b: &&RadarConfig| a.center_frequency.total_cmp(&b.center_frequency);
+        let shortest = steps.iter().max_by(by_frequency).unwrap_or(self).clone();
+        let longest = steps.iter().min_by(by_frequency).unwrap_or(self).clone();
+        (shortest, longest)
+    }
+
+    // Tells whether `sequence` lists every agility step exactly once.
+    pub fn is_agility_sequence(&self, sequence: &[u32]) -> bool {
+        let mut sorted = sequence.to_vec();
+        sorted.sort_unstable();
+        sorted.into_iter().eq(0..self.nb_agilities)
+    }
+
+    // Keeps one frequency offset per agility step and drops the sequence when it
+    // no longer lists every step exactly once.
+    pub fn resize_agilities(&mut self) {
+        self.agility_offsets.resize(self.nb_agilities as usize, 0.0);
+        if !self.is_agility_sequence(&self.agility_sequence) {
+            self.agility_sequence.clear();
+        }
+    }
+
+    // Keeps one set of parameters per receiver channel.
+    pub fn resize_channels(&mut self) {
+        self.channels.resize(self.nb_channels as usize, Channel::default());
+    }
+
+    // Delay of the echo from the scene center in channel `index` relative to the
+    // reference phase centre, in µs: its cable delay and the extra path to its
+    // phase centre, for a plane wave coming under the look angle.
+    pub fn channel_delay(&self, index: usize) -> f64 {
+        let channel = &self.channels[index];
+        let angle = self.receiver_look_angle().to_radians();
+        let [_, across, up] = channel.position;
+        channel.cable_delay * 1e-3 + (up * angle.cos() - across * angle.sin()) / C * 1e6
+    }
+
+    // RX window of channel `index` referred to the reference phase centre, as
+    // `(offset, duration)` in µs: the echoes it samples arrived that much earlier.
+    pub fn channel_rx_window(&self, index: usize) -> (f64, f64) {
+        (self.rx_offset - self.channel_delay(index), self.rx_duration)
+    }
+
+    // Wavelength at the center frequency, in m.
+    pub fn wavelength(&self) -> f64 {
+        C / (self.center_frequency * 1e9)
+    }
+
+    // Look angles sampled regularly across the elevation beam, in degrees.
+    pub fn swath_angles(&self, nb_points: usize) -> Vec<f64> {
+        let (min_angle, max_angle) = self.aperture_angles();
+        (0..nb_points)
+            .map(|i| min_angle + (max_angle - min_angle) * i as f64 / nb_points.saturating_sub(1).max(1) as f64)
+            .filter(|&angle| (0.0..90.0).contains(&angle) && self.slant_range_at(angle).is_finite())
+            .collect()
+    }
+
+    // Velocity of the carrier of the receiver, in m/s.
+    pub fn receiver_velocity(&self) -> f64 {
+        if self.bsar_config { self.rx_carrier_velocity } else { self.carrier_velocity }
+    }
+
+    // Pointing of the receiving antenna, in degrees from the receiver nadir.
+    pub fn receiver_look_angle(&self) -> f64 {
+        if self.bsar_config { self.rx_look_angle } else { self.look_angle }
+    }
+
+    // Time at which the echo from the ground point seen under `angle_deg` comes back, in µs.
+    pub fn echo_time(&self, angle_deg: f64) -> f64 {
+        self.tx_offset + bistatic::range_sum(self, angle_deg) / C * 1e6
+    }
+
+    // Time at which the echo of the pulse from nadir comes back, in µs.
+    pub fn nadir_echo_time(&self) -> f64 {
+        self.echo_time(0.0)
+    }
+
+    // Largest look angle under which the ground is seen, a tenth of a degree short
+    // of the horizon so that the slant range stays finite on a flat earth, in degrees.
+    pub fn max_look_angle(&self) -> f64 {
+        earth::horizon(self.earth_radius(), self.carrier_height) - 0.1
+    }
+
+    // Arrival times of the echoes from the near and far edges of the elevation
+    // beam, edges beyond the horizon brought back to it, in µs.
+    pub fn swath_echo_times(&self) -> (f64, f64) {
+        let (min_angle, max_angle) = self.aperture_angles();
+        let max = self.max_look_angle();
+        (self.echo_time(min_angle.clamp(0.0, max)), self.echo_time(max_angle.clamp(0.0, max)))
+    }
+
+    // RX window offset and duration receiving the whole swath echo plus the margin
+    // on each side, widened to whole sampling periods when `fech` is set, in µs.
+    // None when the echo does not come back within a finite time.
+    pub fn fitted_rx_window(&self) -> Option<(f64, f64)> {
+        let (first, last) = self.swath_echo_times();
+        let start = (first - self.rx_margin).max(0.0);
+        let end = last + self.tx_duration + self.rx_margin;
+        if !(start.is_finite() && end.is_finite() && end >= start) {
+            return None;
+        }
+        if self.fech > 0.0 {
+            let period = 1.0 / self.fech;
+            let start = (start / period).floor() * period;
+            let nb_samples = ((end - start) / period).ceil();
+            Some((start, nb_samples * period))
+        } else {
+            Some((start, end - start))
+        }
+    }
+
+    // Time at which the pulse reaches the receiver straight from the transmitter, in µs.
+    pub fn direct_path_time(&self) -> f64 {
+        self.tx_offset + bistatic::direct_path(self) / C * 1e6
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derived_quantities() {
+        let config = RadarConfig::default();
+        assert_eq!(config.prf(), 10e3);
+        assert_eq!(RadarConfig { nb_agilities: 4, ..config.clone() }.final_prf(), 2.5e3);
+        assert_eq!(config.aperture_angles(), (36.0, 54.0));
+        assert!((config.slant_range() - 3000.0 * 2f64.sqrt()).abs() < 1e-9);
+
+        let (near, far) = config.ground_illumination();
+        assert!((near - 3000.0 * 36f64.to_radians().tan()).abs() < 1e-9);
+        assert!((far - 3000.0 * 54f64.to_radians().tan()).abs() < 1e-9);
+
+        let (start, end) = config.numerization_distances();
+        assert!((start - 0.5 * C * 24e-6).abs() < 1e-6);
+        assert!((end - 0.5 * C * 35e-6).abs() < 1e-6);
+        assert!((config.nadir_echo_time() - 2.0 * 3000.0 / C * 1e6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fitted_rx_window() {
+        let config = RadarConfig { rx_margin: 1.0, fech: 10.0, ..RadarConfig::default() };
+        let (first, last) = config.swath_echo_times();
+        let (offset, duration) = config.fitted_rx_window().unwrap();
+        assert!(offset <= first - 1.0 && offset > first - 1.1);
+        assert!(offset + duration >= last + 11.0 && offset + duration < last + 11.2);
+        assert!(((offset * 10.0) - (offset * 10.0).round()).abs() < 1e-9);
+        assert!(((duration * 10.0) - (duration * 10.0).round()).abs() < 1e-9);
+
+        // At grazing angles the far edge is brought back to the horizon.
+        for earth_model in [EarthModel::Flat, EarthModel::Spherical] {
+            let config = RadarConfig { look_angle: 88.0, earth_model, ..config.clone() };
+            let (offset, duration) = config.fitted_rx_window().unwrap();
+            assert!(offset > 0.0 && duration > 0.0 && (offset + duration).is_finite());
+        }
+        assert!(RadarConfig { carrier_height: f64::INFINITY, ..config }.fitted_rx_window().is_none());
+    }
+
+    #[test]
+    fn agility_plan() {
+        let mut config = RadarConfig { nb_agilities: 3, center_frequency: 10.0, ..RadarConfig::default() };
+        config.resize_agilities();
+        config.agility_offsets = vec![0.0, 50.0, -50.0];
+        config.agility_sequence = vec![2, 0, 1];
+        assert_eq!(config.agility_frequency(1), 10.05);
+        assert!(config.is_agility_sequence(&config.agility_sequence));
+        assert!(!config.is_agility_sequence(&[0, 0, 1]));
+        let frequencies: Vec<f64> = config.agility_steps().iter().map(|s| s.center_frequency).collect();
+        assert_eq!(frequencies, [10.0, 10.05, 9.95]);
+        let (shortest, longest) = config.wavelength_extremes();
+        assert_eq!((shortest.center_frequency, longest.center_frequency), (10.05, 9.95));
+    }
+}
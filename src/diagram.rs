@@ -0,0 +1,57 @@
+use eframe::egui::{Color32, Ui};
+use egui_plot::{Plot, Polygon, Line, Points, Legend, Corner};
+use sarconf::RadarConfig;
+use sarconf::diamond::{self, BandKind};
+
+// PRF versus look angle diagram with the transmit eclipse and nadir bands, the
+// operating point and the elevation beam at the current PRF. Returns the
+// `[look angle (deg), PRF (Hz)]` point clicked by the user, if any.
+pub fn plot(ui: &mut Ui, config: &RadarConfig) -> Option<[f64; 2]> {
+    let prf = config.prf();
+    let max_prf = 3.0 * prf;
+    let (min_angle, max_angle) = config.aperture_angles();
+    let bands = diamond::bands(config, max_prf, 181);
+
+    let legend = Legend::default().position(Corner::RightTop);
+
+    Plot::new("Diamond")
+        .height(300.0)
+        .legend(legend)
+        .allow_scroll(false)
+        .include_x(0.0)
+        .include_x(90.0)
+        .include_y(0.0)
+        .include_y(max_prf)
+        .x_axis_formatter(|x, _| format!("{:.1}°", x.value))
+        .y_axis_formatter(|y, _| format!("{:.0} Hz", y.value))
+        .show(ui, |plot_ui| {
+            for band in bands {
+                let (name, color) = match band.kind {
+                    BandKind::Eclipse => ("Transmit eclipse", Color32::LIGHT_RED),
+                    BandKind::Nadir => ("Nadir echo", Color32::LIGHT_BLUE),
+                };
+                plot_ui.polygon(
+                    Polygon::new(name, band.polygon)
+                        .fill_color(color.gamma_multiply(0.4))
+                        .stroke((1.0, color))
+                );
+            }
+            plot_ui.line(
+                Line::new("Beamwidth", vec![[min_angle.max(0.0), prf], [max_angle, prf]])
+                    .color(Color32::YELLOW)
+                    .width(3.0)
+            );
+            plot_ui.points(
+                Points::new("Operating point", vec![[config.look_angle, prf]])
+                    .radius(5.0)
+                    .color(Color32::YELLOW)
+            );
+
+            if plot_ui.response().clicked() {
+                plot_ui.pointer_coordinate().map(|p| [p.x, p.y])
+            } else {
+                None
+            }
+        })
+        .inner
+}
@@ -0,0 +1,123 @@
+// PRF versus look angle timing diagram. The echo of the point seen under a look
+// angle is lost when it comes back while a pulse is transmitted (transmit
+// eclipse) or together with the nadir echo of an earlier pulse; each condition
+// holds inside a band of PRFs whose edges depend on the look angle.
+use crate::bistatic;
+use crate::config::{C, RadarConfig};
+
+// Orders of the bands drawn at most, however late the echoes come back.
+const MAX_ORDERS: u32 = 256;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BandKind {
+    Eclipse,
+    Nadir,
+}
+
+pub struct Band {
+    pub kind: BandKind,
+    pub order: u32, // Number of PRIs between the pulse and the lost echo
+    pub polygon: Vec<[f64; 2]>, // `[look angle (deg), PRF (Hz)]` points
+}
+
+// Delay of the echo from the point seen under `look_angle_deg` after the start of its pulse, in s.
+fn echo_delay(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    bistatic::range_sum(config, look_angle_deg) / C
+}
+
+// Look angles at which the bands are sampled, up to the horizon.
+fn look_angles(config: &RadarConfig, nb_points: usize) -> Vec<f64> {
+    let radius = config.earth_radius();
+    let max_angle = if radius.is_infinite() {
+        89.0
+    } else {
+        (radius / (radius + config.carrier_height)).asin().to_degrees().min(89.0)
+    };
+    (0..nb_points)
+        .map(|i| max_angle * i as f64 / nb_points.saturating_sub(1).max(1) as f64)
+        .collect()
+}
+
+// Band between the lower and upper PRFs given by `edges` at each look angle, clipped to `max_prf`,
+// or None when it lies entirely above `max_prf`.
+fn band(kind: BandKind, order: u32, angles: &[f64], edges: impl Fn(f64) -> (f64, f64), max_prf: f64) -> Option<Band> {
+    let (lower, upper): (Vec<[f64; 2]>, Vec<[f64; 2]>) = angles.iter()
+        .map(|&angle| {
+            let (low, high) = edges(angle);
+            let high = if high > 0.0 && high.is_finite() { high.min(max_prf) } else { max_prf };
+            ([angle, low.min(max_prf)], [angle, high])
+        })
+        .filter(|([_, low], _)| low.is_finite())
+        .unzip();
+    if lower.iter().all(|[_, low]| *low >= max_prf) {
+        return None;
+    }
+    let mut polygon = lower;
+    polygon.extend(upper.into_iter().rev());
+    Some(Band { kind, order, polygon })
+}
+
+// Transmit eclipse and nadir bands below `max_prf` (Hz) for the current height,
+// pulse duration and earth model.
+pub fn bands(config: &RadarConfig, max_prf: f64, nb_points: usize) -> Vec<Band> {
+    let tau = config.tx_duration * 1e-6;
+    let angles = look_angles(config, nb_points);
+    let nadir_delay = echo_delay(config, 0.0);
+
+    // Bands of higher orders start above `max_prf`, even for the latest echo.
+    let max_delay = angles.iter().map(|&angle| echo_delay(config, angle)).fold(0.0, f64::max);
+    let max_order = (max_prf * (max_delay + tau)).ceil().min(MAX_ORDERS as f64) as u32;
+
+    let mut bands = Vec::new();
+    for order in 1..=max_order {
+        let n = order as f64;
+        // The echo [t, t + τ] meets the pulse [n·PRI, n·PRI + τ].
+        let eclipse = band(BandKind::Eclipse, order, &angles, |angle| {
+            let t = echo_delay(config, angle);
+            (n / (t + tau), n / (t - tau))
+        }, max_prf);
+        // The echo [t, t + τ] meets the nadir echo [n·PRI + tn, n·PRI + tn + τ].
+        let nadir = band(BandKind::Nadir, order, &angles, |angle| {
+            let t = echo_delay(config, angle) - nadir_delay;
+            (n / (t + tau), n / (t - tau))
+        }, max_prf);
+        bands.extend(eclipse);
+        bands.extend(nadir);
+    }
+    bands
+}
+
+// Tells whether the echo from `look_angle_deg` is lost at `prf` (Hz).
+pub fn is_blind(config: &RadarConfig, look_angle_deg: f64, prf: f64) -> bool {
+    let tau = config.tx_duration * 1e-6;
+    let t = echo_delay(config, look_angle_deg);
+    let tn = t - echo_delay(config, 0.0);
+    let pri = 1.0 / prf;
+    let hits = |t: f64| {
+        let n = (t / pri).round();
+        n >= 1.0 && (t - n * pri).abs() < tau
+    };
+    hits(t) || hits(tn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bands_match_blind_points() {
+        let config = RadarConfig { carrier_height: 10000.0, ..RadarConfig::default() };
+        let bands = bands(&config, 20e3, 91);
+        assert!(bands.iter().any(|b| b.kind == BandKind::Eclipse && b.order == 1));
+        assert!(bands.iter().any(|b| b.kind == BandKind::Nadir && b.order == 1));
+
+        // Middle of the first eclipse band at 45°: the echo comes back one PRI later.
+        let t = echo_delay(&config, 45.0);
+        assert!(is_blind(&config, 45.0, 1.0 / t));
+        assert!(!is_blind(&config, 45.0, 1.0 / (t - 3.0 * config.tx_duration * 1e-6)));
+
+        // From a satellite, thousands of PRIs fit in the echo delay near the horizon.
+        let config = RadarConfig { carrier_height: 700e3, ..RadarConfig::default() };
+        assert!(super::bands(&config, 1e6, 91).len() <= 2 * MAX_ORDERS as usize);
+    }
+}
@@ -0,0 +1,142 @@
+// Geometry of a radar flying at `height` above a surface of radius `radius`, in
+// the vertical plane containing the line of sight. A flat earth is a surface of
+// infinite radius. Angles are in degrees from nadir, distances in m.
+
+pub const MEAN_RADIUS: f64 = 6371008.8; // IUGG mean radius, m
+const WGS84_A: f64 = 6378137.0; // m
+const WGS84_F: f64 = 1.0 / 298.257223563;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EarthModel {
+    Flat,
+    Spherical,
+    Wgs84,
+}
+
+impl EarthModel {
+    pub const ALL: [EarthModel; 3] = [EarthModel::Flat, EarthModel::Spherical, EarthModel::Wgs84];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            EarthModel::Flat => "Flat",
+            EarthModel::Spherical => "Spherical",
+            EarthModel::Wgs84 => "WGS84",
+        }
+    }
+
+    // Identifier used in the configuration files.
+    pub fn key(self) -> &'static str {
+        match self {
+            EarthModel::Flat => "flat",
+            EarthModel::Spherical => "spherical",
+            EarthModel::Wgs84 => "wgs84",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<EarthModel> {
+        EarthModel::ALL.into_iter().find(|m| m.key() == key)
+    }
+
+    // Radius of the surface under the radar. The ellipsoid is replaced by its
+    // osculating sphere at `latitude_deg`, whose radius is the geometric mean of
+    // the meridian and prime vertical radii of curvature.
+    pub fn radius(self, latitude_deg: f64) -> f64 {
+        match self {
+            EarthModel::Flat => f64::INFINITY,
+            EarthModel::Spherical => MEAN_RADIUS,
+            EarthModel::Wgs84 => {
+                let e2 = WGS84_F * (2.0 - WGS84_F);
+                let w2 = 1.0 - e2 * latitude_deg.to_radians().sin().powi(2);
+                WGS84_A * (1.0 - e2).sqrt() / w2
+            }
+        }
+    }
+}
+
+// Look angle of the horizon, 90° on a flat earth.
+pub fn horizon(radius: f64, height: f64) -> f64 {
+    if radius.is_infinite() {
+        return 90.0;
+    }
+    (radius / (radius + height)).asin().to_degrees()
+}
+
+// Distance to the surface along the direction `look_angle_deg`, NaN beyond the horizon.
+pub fn slant_range(radius: f64, height: f64, look_angle_deg: f64) -> f64 {
+    let (sin, cos) = look_angle_deg.to_radians().sin_cos();
+    if radius.is_infinite() {
+        return height / cos;
+    }
+    let orbit = radius + height;
+    orbit * cos - (radius * radius - orbit * orbit * sin * sin).sqrt()
+}
+
+// Angle between the line of sight and the local vertical at the surface.
+pub fn incidence_angle(radius: f64, height: f64, look_angle_deg: f64) -> f64 {
+    if radius.is_infinite() {
+        return look_angle_deg;
+    }
+    ((radius + height) / radius * look_angle_deg.to_radians().sin()).asin().to_degrees()
+}
+
+// Distance from nadir along the surface to the point seen under `look_angle_deg`.
+pub fn ground_range(radius: f64, height: f64, look_angle_deg: f64) -> f64 {
+    if radius.is_infinite() {
+        return height * look_angle_deg.to_radians().tan();
+    }
+    radius * (incidence_angle(radius, height, look_angle_deg) - look_angle_deg).to_radians()
+}
+
+// Look angle under which the surface is seen at `slant_range`, NaN when that
+// range is shorter than the height or beyond the horizon.
+pub fn look_angle(radius: f64, height: f64, slant_range: f64) -> f64 {
+    if slant_range < height {
+        return f64::NAN;
+    }
+    if radius.is_infinite() {
+        return (height / slant_range).acos().to_degrees();
+    }
+    if slant_range * slant_range > height * (2.0 * radius + height) {
+        return f64::NAN;
+    }
+    let orbit = radius + height;
+    ((height * (2.0 * radius + height) + slant_range * slant_range) / (2.0 * slant_range * orbit))
+        .min(1.0)
+        .acos()
+        .to_degrees()
+}
+
+// Position of the point seen under `look_angle_deg` relative to nadir, as
+// `[horizontal, vertical]` offsets in the plane of the line of sight.
+pub fn surface_point(radius: f64, height: f64, look_angle_deg: f64) -> [f64; 2] {
+    if radius.is_infinite() {
+        return [ground_range(radius, height, look_angle_deg), 0.0];
+    }
+    let alpha = ground_range(radius, height, look_angle_deg) / radius;
+    [radius * alpha.sin(), -2.0 * radius * (alpha / 2.0).sin().powi(2)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curved_geometry() {
+        let (radius, height) = (MEAN_RADIUS, 10000.0);
+        let range = slant_range(radius, height, 60.0);
+        assert!(range > slant_range(f64::INFINITY, height, 60.0));
+        assert!((look_angle(radius, height, range) - 60.0).abs() < 1e-9);
+
+        // The point lies on the sphere and at `range` from the radar.
+        let [x, y] = surface_point(radius, height, 60.0);
+        assert!((x.hypot(y + radius) - radius).abs() < 1e-6);
+        assert!((x.hypot(height - y) - range).abs() < 1e-6);
+        assert!(incidence_angle(radius, height, 60.0) > 60.0);
+
+        let horizon = horizon(radius, height);
+        assert!(slant_range(radius, height, horizon + 0.1).is_nan());
+        assert!(look_angle(radius, height, 2.0 * (height * 2.0 * radius).sqrt()).is_nan());
+        assert!((EarthModel::Wgs84.radius(0.0) - 6356752.3).abs() < 1.0);
+        assert!(EarthModel::Wgs84.radius(45.0) < EarthModel::Wgs84.radius(90.0));
+    }
+}
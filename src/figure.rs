@@ -0,0 +1,349 @@
+// Plots described independently of the drawing backend, so that the application
+// draws them and the exported SVG and PNG files show the same content.
+use std::fmt::Write;
+use std::path::Path;
+
+pub type Rgb = [u8; 3];
+
+// Colors of the egui palette used by the plots.
+pub const RED: Rgb = [255, 0, 0];
+pub const LIGHT_RED: Rgb = [255, 128, 128];
+pub const GOLD: Rgb = [255, 215, 0];
+pub const YELLOW: Rgb = [255, 255, 0];
+pub const LIGHT_YELLOW: Rgb = [255, 255, 224];
+pub const DARK_GREEN: Rgb = [0, 100, 0];
+pub const LIGHT_GREEN: Rgb = [144, 238, 144];
+pub const BLUE: Rgb = [0, 0, 255];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const LIGHT_GRAY: Rgb = [220, 220, 220];
+pub const GRAY: Rgb = [160, 160, 160];
+
+// Exported images use the dark theme of the application.
+const BACKGROUND: Rgb = [27, 27, 27];
+const FOREGROUND: Rgb = [200, 200, 200];
+const GRID: Rgb = [60, 60, 60];
+const FONT_FAMILY: &str = "Ubuntu";
+const FONT_SIZE: f64 = 12.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Style {
+    Solid,
+    Dashed,
+}
+
+pub struct Series {
+    pub name: String,
+    pub points: Vec<[f64; 2]>,
+    pub color: Rgb,
+    pub width: f32,
+    pub style: Style,
+    pub fill: Option<(f64, f32)>, // Ordinate the area under the line is filled down to, and its opacity
+    pub marker: bool, // Drawn as points rather than as a line
+    pub highlighted: bool,
+}
+
+impl Default for Series {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            points: Vec::new(),
+            color: WHITE,
+            width: 1.5,
+            style: Style::Solid,
+            fill: None,
+            marker: false,
+            highlighted: false,
+        }
+    }
+}
+
+// `[[x min, y min], [x max, y max]]` of the displayed area.
+pub type Bounds = [[f64; 2]; 2];
+
+pub struct Figure {
+    pub title: String,
+    pub x_unit: &'static str,
+    pub y_unit: &'static str,
+    pub show_y: bool, // Y axis and grid, hidden when the ordinate has no meaning
+    pub equal_aspect: bool, // Same scale on both axes
+    pub x_include: Vec<f64>, // Abscissas always shown by the default bounds
+    pub y_bounds: Option<[f64; 2]>, // Ordinates shown by the default bounds, instead of fitting the data
+    pub size: [u32; 2], // Default size of the exported images, in px
+    pub series: Vec<Series>,
+}
+
+impl Default for Figure {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            x_unit: "",
+            y_unit: "",
+            show_y: true,
+            equal_aspect: false,
+            x_include: Vec::new(),
+            y_bounds: None,
+            size: [1200, 600],
+            series: Vec::new(),
+        }
+    }
+}
+
+impl Figure {
+    // Bounds fitting every point with a 5 % margin, as the application shows them first.
+    pub fn bounds(&self) -> Bounds {
+        // `[min, max]` of each axis.
+        let mut ranges = [[f64::INFINITY, f64::NEG_INFINITY]; 2];
+        let points = self.series.iter().flat_map(|s| s.points.iter().copied());
+        let included = self.x_include.iter().map(|&x| [x, f64::NAN]);
+        for point in points.chain(included) {
+            for (range, value) in ranges.iter_mut().zip(point) {
+                if value.is_finite() {
+                    *range = [range[0].min(value), range[1].max(value)];
+                }
+            }
+        }
+        for range in &mut ranges {
+            if range[0] > range[1] {
+                *range = [0.0, 1.0];
+            }
+            let margin = 0.05 * (range[1] - range[0]).max(1e-9);
+            *range = [range[0] - margin, range[1] + margin];
+        }
+        if let Some(y_bounds) = self.y_bounds {
+            ranges[1] = y_bounds;
+        }
+        let [[x_min, x_max], [y_min, y_max]] = ranges;
+        [[x_min, y_min], [x_max, y_max]]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ImageFormat {
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    pub const ALL: [ImageFormat; 2] = [ImageFormat::Png, ImageFormat::Svg];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "PNG",
+            ImageFormat::Svg => "SVG",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Svg => "svg",
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<ImageFormat> {
+        let extension = path.extension()?.to_str()?;
+        ImageFormat::ALL.into_iter().find(|f| f.extension().eq_ignore_ascii_case(extension))
+    }
+
+    pub fn render(self, figure: &Figure, bounds: Bounds) -> Result<Vec<u8>, String> {
+        match self {
+            ImageFormat::Png => to_png(figure, bounds),
+            ImageFormat::Svg => Ok(to_svg(figure, bounds).into_bytes()),
+        }
+    }
+}
+
+fn rgb(color: Rgb) -> String {
+    format!("rgb({},{},{})", color[0], color[1], color[2])
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// Round step giving about `count` intervals over `span`: 1, 2 or 5 times a power of ten.
+fn tick_step(span: f64, count: f64) -> f64 {
+    let raw = span / count;
+    let power = 10f64.powf(raw.log10().floor());
+    [1.0, 2.0, 5.0, 10.0].into_iter().map(|m| m * power).find(|&step| step >= raw).unwrap_or(10.0 * power)
+}
+
+fn ticks(min: f64, max: f64, count: f64) -> Vec<f64> {
+    let step = tick_step(max - min, count);
+    let first = (min / step).ceil() as i64;
+    let last = (max / step).floor() as i64;
+    (first..=last).map(|i| i as f64 * step).collect()
+}
+
+// Draws the figure over `bounds` in an SVG document of `figure.size`, with the
+// legend in a column on the right of the plot.
+pub fn to_svg(figure: &Figure, bounds: Bounds) -> String {
+    let [width, height] = figure.size.map(f64::from);
+    let mut names: Vec<(&str, &Series)> = Vec::new();
+    for series in &figure.series {
+        if !series.name.is_empty() && !names.iter().any(|(name, _)| *name == series.name) {
+            names.push((&series.name, series));
+        }
+    }
+    // Legend entries wrap into as many columns as needed to fit the height.
+    let longest = names.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+    let column_width = 40.0 + 0.6 * FONT_SIZE * longest as f64;
+    let rows = (((height - 45.0) / 18.0).floor() as usize).max(1);
+    let legend_width = names.len().div_ceil(rows) as f64 * column_width;
+
+    // Plot area, in px.
+    let left = if figure.show_y { 80.0 } else { 15.0 };
+    let (x0, x1) = (left, width - legend_width - 15.0);
+    let (y0, y1) = (35.0, height - 30.0);
+    let (plot_width, plot_height) = (x1 - x0, y1 - y0);
+
+    let [[mut bx0, mut by0], [mut bx1, mut by1]] = bounds;
+    if figure.equal_aspect {
+        // Widen the axis with the finer scale around its center.
+        let scale = ((bx1 - bx0) / plot_width).max((by1 - by0) / plot_height);
+        let (cx, cy) = ((bx0 + bx1) / 2.0, (by0 + by1) / 2.0);
+        (bx0, bx1) = (cx - scale * plot_width / 2.0, cx + scale * plot_width / 2.0);
+        (by0, by1) = (cy - scale * plot_height / 2.0, cy + scale * plot_height / 2.0);
+    }
+    let px = |x: f64| x0 + (x - bx0) / (bx1 - bx0) * plot_width;
+    let py = |y: f64| y1 - (y - by0) / (by1 - by0) * plot_height;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="{FONT_FAMILY}, sans-serif" font-size="{FONT_SIZE}">"#
+    ).unwrap();
+    writeln!(svg, r#"<rect width="100%" height="100%" fill="{}"/>"#, rgb(BACKGROUND)).unwrap();
+    writeln!(svg, r#"<clipPath id="plot"><rect x="{x0}" y="{y0}" width="{plot_width}" height="{plot_height}"/></clipPath>"#).unwrap();
+    writeln!(
+        svg,
+        r#"<text x="{}" y="22" text-anchor="middle" font-size="{}" fill="{}">{}</text>"#,
+        (x0 + x1) / 2.0, FONT_SIZE + 4.0, rgb(FOREGROUND), escape(&figure.title)
+    ).unwrap();
+
+    // Grid and axis labels.
+    for x in ticks(bx0, bx1, plot_width / 120.0) {
+        writeln!(svg, r#"<line x1="{0}" y1="{y0}" x2="{0}" y2="{y1}" stroke="{1}"/>"#, px(x), rgb(GRID)).unwrap();
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle" fill="{}">{:.1} {}</text>"#,
+            px(x), y1 + 18.0, rgb(FOREGROUND), x, escape(figure.x_unit)
+        ).unwrap();
+    }
+    if figure.show_y {
+        for y in ticks(by0, by1, plot_height / 60.0) {
+            writeln!(svg, r#"<line x1="{x0}" y1="{0}" x2="{x1}" y2="{0}" stroke="{1}"/>"#, py(y), rgb(GRID)).unwrap();
+            writeln!(
+                svg,
+                r#"<text x="{}" y="{}" text-anchor="end" fill="{}">{:.1} {}</text>"#,
+                x0 - 6.0, py(y) + 4.0, rgb(FOREGROUND), y, escape(figure.y_unit)
+            ).unwrap();
+        }
+    }
+    writeln!(svg, r#"<rect x="{x0}" y="{y0}" width="{plot_width}" height="{plot_height}" fill="none" stroke="{}"/>"#, rgb(GRID)).unwrap();
+
+    writeln!(svg, r#"<g clip-path="url(#plot)" fill="none" stroke-linejoin="round">"#).unwrap();
+    for series in &figure.series {
+        let points: Vec<[f64; 2]> = series.points.iter()
+            .filter(|[x, y]| x.is_finite() && y.is_finite())
+            .map(|&[x, y]| [px(x), py(y)])
+            .collect();
+        let color = rgb(series.color);
+        if series.marker {
+            for [x, y] in points {
+                writeln!(svg, r#"<circle cx="{x:.2}" cy="{y:.2}" r="4" fill="{color}"/>"#).unwrap();
+            }
+            continue;
+        }
+        let path: Vec<String> = points.iter().map(|[x, y]| format!("{:.2},{:.2}", x, y)).collect();
+        // Highlighted lines are drawn twice as wide and filled twice as opaque, as by egui_plot.
+        let scale = if series.highlighted { 2.0 } else { 1.0 };
+        if let (Some((base, alpha)), Some(first), Some(last)) = (series.fill, points.first(), points.last()) {
+            let (base, alpha) = (py(base), (scale * alpha).min(1.0));
+            writeln!(
+                svg,
+                r#"<polygon points="{} {:.2},{base:.2} {:.2},{base:.2}" fill="{color}" fill-opacity="{alpha}" stroke="none"/>"#,
+                path.join(" "), last[0], first[0]
+            ).unwrap();
+        }
+        let dash = match series.style {
+            Style::Solid => String::new(),
+            Style::Dashed => r#" stroke-dasharray="5 5""#.to_string(),
+        };
+        writeln!(
+            svg,
+            r#"<polyline points="{}" stroke="{color}" stroke-width="{}"{dash}/>"#,
+            path.join(" "), scale * series.width
+        ).unwrap();
+    }
+    writeln!(svg, "</g>").unwrap();
+
+    // Legend, one entry per name as in the application.
+    for (i, (name, series)) in names.iter().enumerate() {
+        let legend_x = x1 + 15.0 + column_width * (i / rows) as f64;
+        let y = y0 + 8.0 + 18.0 * (i % rows) as f64;
+        let color = rgb(series.color);
+        if series.marker {
+            writeln!(svg, r#"<circle cx="{}" cy="{y}" r="4" fill="{color}"/>"#, legend_x + 8.0).unwrap();
+        } else {
+            writeln!(svg, r#"<line x1="{legend_x}" y1="{y}" x2="{}" y2="{y}" stroke="{color}" stroke-width="3"/>"#, legend_x + 16.0).unwrap();
+        }
+        writeln!(svg, r#"<text x="{}" y="{}" fill="{}">{}</text>"#, legend_x + 24.0, y + 4.0, rgb(FOREGROUND), escape(name)).unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// Rasterises the SVG rendering of the figure, with the font of the application
+// so that the result does not depend on the fonts installed.
+pub fn to_png(figure: &Figure, bounds: Bounds) -> Result<Vec<u8>, String> {
+    let mut options = resvg::usvg::Options {
+        font_family: FONT_FAMILY.to_string(),
+        ..Default::default()
+    };
+    options.fontdb_mut().load_font_data(epaint_default_fonts::UBUNTU_LIGHT.to_vec());
+    let tree = resvg::usvg::Tree::from_str(&to_svg(figure, bounds), &options).map_err(|err| err.to_string())?;
+    let [width, height] = figure.size;
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| format!("invalid image size {}×{}", width, height))?;
+    resvg::render(&tree, resvg::tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    pixmap.encode_png().map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export() {
+        let figure = Figure {
+            title: "Test <1>".to_string(),
+            x_unit: "m",
+            size: [400, 200],
+            series: vec![
+                Series { name: "Line".to_string(), points: vec![[0.0, 0.0], [10.0, 5.0]], ..Default::default() },
+                Series { name: "Line".to_string(), points: vec![[0.0, 5.0], [10.0, 0.0]], ..Default::default() },
+                Series { name: "Point".to_string(), points: vec![[5.0, 2.5]], marker: true, ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let bounds = figure.bounds();
+        assert_eq!(bounds, [[-0.5, -0.25], [10.5, 5.25]]);
+
+        let svg = to_svg(&figure, bounds);
+        assert!(svg.contains("Test &lt;1&gt;"));
+        assert_eq!(svg.matches(">Line</text>").count(), 1);
+        assert!(svg.contains(">Point</text>"));
+        assert!(svg.contains("10.0 m"));
+
+        let highlighted = Figure {
+            series: vec![Series { points: vec![[0.0, 0.0], [10.0, 5.0]], width: 2.0, fill: Some((0.0, 0.3)), highlighted: true, ..Default::default() }],
+            ..Default::default()
+        };
+        let svg = to_svg(&highlighted, bounds);
+        assert!(svg.contains(r#"fill-opacity="0.6""#) && svg.contains(r#"stroke-width="4""#));
+
+        let png = ImageFormat::Png.render(&figure, [[2.0, 0.0], [4.0, 1.0]]).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
@@ -0,0 +1,62 @@
+use std::path::Path;
+use crate::config::RadarConfig;
+use crate::import::Imported;
+use crate::{genio, json, pamela, sameva, vst};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    Json,
+    Sameva,
+    Genio,
+    Vst,
+    Pamela,
+}
+
+impl Format {
+    pub const ALL: [Format; 5] = [Format::Json, Format::Sameva, Format::Genio, Format::Vst, Format::Pamela];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Json => "JSON",
+            Format::Sameva => "SAMEVA",
+            Format::Genio => "GENIO",
+            Format::Vst => "VST",
+            Format::Pamela => "PAMELA",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Sameva => "init",
+            Format::Genio => "io",
+            Format::Vst => "vst",
+            Format::Pamela => "wave",
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?;
+        Format::ALL.into_iter().find(|f| f.extension().eq_ignore_ascii_case(extension))
+    }
+
+    pub fn export(self, config: &RadarConfig) -> Result<Vec<u8>, String> {
+        match self {
+            Format::Json => Ok(json::to_string(config).into_bytes()),
+            Format::Sameva => Ok(sameva::to_string(config).into_bytes()),
+            Format::Genio => genio::to_string(config).map(String::into_bytes),
+            Format::Vst => Ok(vst::to_string(config).into_bytes()),
+            Format::Pamela => pamela::to_bytes(config),
+        }
+    }
+
+    pub fn import(self, bytes: &[u8]) -> Imported {
+        match self {
+            Format::Json => json::from_bytes(bytes),
+            Format::Sameva => sameva::from_bytes(bytes),
+            Format::Genio => genio::from_bytes(bytes),
+            Format::Vst => vst::from_bytes(bytes),
+            Format::Pamela => pamela::from_bytes(bytes),
+        }
+    }
+}
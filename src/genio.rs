@@ -0,0 +1,260 @@
+// GENIO files program the digitiser: one `KEY value` per line, every time
+// expressed as a number of samples at the I/Q sampling frequency, `#` comments.
+use std::fmt::Write;
+use crate::config::RadarConfig;
+use crate::import::{self, Imported, Mapping};
+use crate::pri::PriMode;
+
+const MAPPINGS: &[Mapping] = &[
+    Mapping { key: "FECH", field: "rx.fech", scale: 1e-6 },
+    Mapping { key: "PRI", field: "tx.pri", scale: 1.0 },
+    Mapping { key: "NB_AGILITIES", field: "tx.nb_agilities", scale: 1.0 },
+    Mapping { key: "NB_CHANNELS", field: "rx.nb_channels", scale: 1.0 },
+    Mapping { key: "TX_OFFSET", field: "tx.offset", scale: 1.0 },
+    Mapping { key: "TX_LENGTH", field: "tx.duration", scale: 1.0 },
+    Mapping { key: "RX_OFFSET", field: "rx.offset", scale: 1.0 },
+    Mapping { key: "RX_LENGTH", field: "rx.duration", scale: 1.0 },
+    Mapping { key: "NOISE_OFFSET", field: "rx.noise_offset", scale: 1.0 },
+    Mapping { key: "NOISE_LENGTH", field: "rx.noise_duration", scale: 1.0 },
+    Mapping { key: "REINJ_OFFSET", field: "rx.reinj_offset", scale: 1.0 },
+    Mapping { key: "REINJ_LENGTH", field: "rx.reinj_duration", scale: 1.0 },
+];
+
+// Keys holding a number of samples, converted to µs once FECH is known.
+const SAMPLE_KEYS: &[&str] = &[
+    "PRI", "TX_OFFSET", "TX_LENGTH", "RX_OFFSET", "RX_LENGTH",
+    "NOISE_OFFSET", "NOISE_LENGTH", "REINJ_OFFSET", "REINJ_LENGTH",
+];
+
+fn samples(time_us: f64, fech_mhz: f64) -> u64 {
+    (time_us * fech_mhz).round() as u64
+}
+
+fn entry(out: &mut String, key: &str, value: impl std::fmt::Display, comment: &str) {
+    writeln!(out, "{:<14} {:<12} # {}", key, value, comment).unwrap();
+}
+
+// Window `(offset, duration)` in µs with both edges moved to the nearest sample,
+// so that the programmed window ends where the chronogram shows it.
+pub fn quantise(offset_us: f64, duration_us: f64, fech_mhz: f64) -> (f64, f64) {
+    let start = samples(offset_us, fech_mhz) as f64 / fech_mhz;
+    let end = samples(offset_us + duration_us, fech_mhz) as f64 / fech_mhz;
+    (start, end - start)
+}
+
+// Configuration with the PRIs and the windows on the sample clock, as the GENIO
+// file programs them, which the chronogram and the timing rules show. Unchanged
+// without a sampling frequency.
+pub fn programmed(config: &RadarConfig) -> RadarConfig {
+    let mut programmed = config.clone();
+    if config.fech <= 0.0 {
+        return programmed;
+    }
+    let fech = config.fech;
+    programmed.pri = samples(config.pri, fech) as f64 / fech;
+    // Staggered and jittered PRIs fall between samples even with a quantised
+    // stagger or jitter, so the sequence is programmed as the list of its PRIs.
+    if config.pri_mode != PriMode::Constant {
+        programmed.pri_list = config.raw_pri_sequence().iter().map(|&pri| samples(pri, fech) as f64 / fech).collect();
+        programmed.pri_mode = PriMode::List;
+    }
+    (programmed.tx_offset, programmed.tx_duration) = quantise(config.tx_offset, config.tx_duration, fech);
+    (programmed.rx_offset, programmed.rx_duration) = quantise(config.rx_offset, config.rx_duration, fech);
+    (programmed.rx_noise_offset, programmed.rx_noise_duration) = quantise(config.rx_noise_offset, config.rx_noise_duration, fech);
+    (programmed.rx_reinj_offset, programmed.rx_reinj_duration) = quantise(config.rx_reinj_offset, config.rx_reinj_duration, fech);
+    programmed
+}
+
+fn window(out: &mut String, name: &str, offset_us: f64, duration_us: f64, fech_mhz: f64) {
+    let start = samples(offset_us, fech_mhz);
+    let end = samples(offset_us + duration_us, fech_mhz);
+    entry(out, &format!("{}_OFFSET", name), start, &format!("{:.3} µs", start as f64 / fech_mhz));
+    entry(out, &format!("{}_LENGTH", name), end - start, &format!("{:.3} µs", (end - start) as f64 / fech_mhz));
+}
+
+pub fn to_string(config: &RadarConfig) -> Result<String, String> {
+    if config.fech <= 0.0 {
+        return Err("GENIO export requires a sampling frequency (Fech I/O) above 0 MHz".to_string());
+    }
+    let pri = samples(config.pri, config.fech);
+
+    let mut out = String::new();
+    writeln!(out, "# GENIO acquisition timing generated by SARConf").unwrap();
+    writeln!(out, "# Configuration: {}", config.config_name).unwrap();
+    entry(&mut out, "FECH", config.fech * 1e6, "Hz");
+    entry(&mut out, "PRI", pri, &format!("{:.3} µs", pri as f64 / config.fech));
+    entry(&mut out, "NB_AGILITIES", config.nb_agilities, "agility steps");
+    for (step, offset) in config.agility_offsets.iter().enumerate() {
+        entry(&mut out, &format!("AGILITY{}_OFFSET", step), offset * 1e6, "Hz from the center frequency");
+    }
+    let sequence: Vec<String> = config.agility_sequence().iter().map(u32::to_string).collect();
+    entry(&mut out, "AGILITY_SEQUENCE", sequence.join(","), "agility steps in transmission order");
+    entry(&mut out, "NB_CHANNELS", config.nb_channels, "receiver channels");
+    for (i, channel) in config.channels.iter().enumerate() {
+        let [along, across, up] = channel.position;
+        entry(&mut out, &format!("CH{}_ENABLE", i), channel.enabled as u8, &format!("channel {}", i));
+        entry(&mut out, &format!("CH{}_DELAY", i), channel.cable_delay, "ns");
+        entry(&mut out, &format!("CH{}_GAIN", i), channel.gain, "dB");
+        entry(&mut out, &format!("CH{}_ALONG", i), along, "m");
+        entry(&mut out, &format!("CH{}_ACROSS", i), across, "m");
+        entry(&mut out, &format!("CH{}_UP", i), up, "m");
+    }
+    window(&mut out, "TX", config.tx_offset, config.tx_duration, config.fech);
+    window(&mut out, "RX", config.rx_offset, config.rx_duration, config.fech);
+    window(&mut out, "NOISE", config.rx_noise_offset, config.rx_noise_duration, config.fech);
+    window(&mut out, "REINJ", config.rx_reinj_offset, config.rx_reinj_duration, config.fech);
+    Ok(out)
+}
+
+pub fn from_bytes(bytes: &[u8]) -> Imported {
+    let mut name = None;
+    let mut entries = Vec::new();
+    let mut channels = Vec::new();
+    let mut offsets = Vec::new();
+    let mut sequence = None;
+    let mut errors = Vec::new();
+    for (number, line) in import::text(bytes)?.lines().enumerate() {
+        if let Some(config_name) = line.strip_prefix("# Configuration: ") {
+            name = Some(config_name.to_string());
+        }
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(char::is_whitespace) else {
+            errors.push(format!("line {}: expected `KEY value`", number + 1));
+            continue;
+        };
+        if key == "AGILITY_SEQUENCE" {
+            match import::parse_sequence(value.trim()) {
+                Some(steps) => sequence = Some(steps),
+                None => errors.push(format!("line {}: invalid agility sequence \"{}\"", number + 1, value.trim())),
+            }
+            continue;
+        }
+        match value.trim().parse::<f64>() {
+            Ok(value) if key.starts_with("CH") => match channel_key(key) {
+                Some((index, parameter)) => channels.push((key.to_string(), index, parameter, value)),
+                None => entries.push((key.to_string(), value)),
+            },
+            Ok(value) if key.starts_with("AGILITY") => match agility_key(key) {
+                Some(step) => offsets.push((key.to_string(), step, value / 1e6)),
+                None => entries.push((key.to_string(), value)),
+            },
+            Ok(value) => entries.push((key.to_string(), value)),
+            Err(_) => errors.push(format!("line {}: invalid number \"{}\"", number + 1, value.trim())),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let fech_mhz = match entries.iter().find(|(key, _)| key == "FECH") {
+        Some((_, fech)) if *fech > 0.0 => fech * 1e-6,
+        _ => return Err(vec!["FECH: a sampling frequency above 0 Hz is required to convert sample counts".to_string()]),
+    };
+    for (key, value) in &mut entries {
+        if SAMPLE_KEYS.contains(&key.as_str()) {
+            *value /= fech_mhz;
+        }
+    }
+    let (mut config, mut warnings) = import::apply("GENIO", name, entries, MAPPINGS)?;
+    import::apply_agilities(&mut config, &mut warnings, offsets, sequence.map(|s| ("AGILITY_SEQUENCE", s)))?;
+
+    for (key, index, parameter, value) in channels {
+        let Some(channel) = config.channels.get_mut(index) else {
+            warnings.push(format!("{}: no such receiver channel, ignored", key));
+            continue;
+        };
+        match parameter {
+            "ENABLE" => channel.enabled = value != 0.0,
+            "DELAY" => channel.cable_delay = value,
+            "GAIN" => channel.gain = value,
+            "ALONG" => channel.position[0] = value,
+            "ACROSS" => channel.position[1] = value,
+            _ => channel.position[2] = value,
+        }
+    }
+    Ok((config, warnings))
+}
+
+// Agility step of an `AGILITY<step>_OFFSET` key.
+fn agility_key(key: &str) -> Option<usize> {
+    key.strip_prefix("AGILITY")?.strip_suffix("_OFFSET")?.parse().ok()
+}
+
+// Channel index and parameter of a `CH<index>_<parameter>` key.
+fn channel_key(key: &str) -> Option<(usize, &str)> {
+    let (index, parameter) = key.strip_prefix("CH")?.split_once('_')?;
+    let parameter = ["ENABLE", "DELAY", "GAIN", "ALONG", "ACROSS", "UP"].into_iter().find(|p| *p == parameter)?;
+    Some((index.parse().ok()?, parameter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Channel;
+
+    #[test]
+    fn channel_layout_round_trip() {
+        let mut config = RadarConfig { fech: 100.0, nb_channels: 2, ..RadarConfig::default() };
+        config.resize_channels();
+        config.channels[1] = Channel { position: [0.5, -0.25, 0.1], cable_delay: 12.5, gain: -3.0, enabled: false };
+        let (imported, _) = from_bytes(to_string(&config).unwrap().as_bytes()).unwrap();
+        assert!(imported.channels == config.channels);
+
+        let (_, warnings) = from_bytes(b"FECH 100e6\nCH3_GAIN 2\n").unwrap();
+        assert!(warnings.iter().any(|w| w.starts_with("CH3_GAIN: no such receiver channel")));
+        let errors = from_bytes(b"FECH 100e6\nNB_CHANNELS 4000000000\n").err().unwrap();
+        assert_eq!(errors, ["NB_CHANNELS: 4000000000 count is out of range [1, 64]"]);
+    }
+
+    #[test]
+    fn programmed_timing() {
+        let config = RadarConfig {
+            fech: 10.0,
+            rx_offset: 24.02,
+            rx_duration: 21.0,
+            rx_noise_offset: 15.0,
+            rx_noise_duration: 9.04,
+            ..RadarConfig::default()
+        };
+        let text = to_string(&config).unwrap();
+        assert!(text.contains("RX_OFFSET      240 "));
+        assert!(text.contains("NOISE_LENGTH   90 "));
+
+        // The chronogram and the rules see the windows the file programs, which
+        // no longer overlap once both edges are on the sample clock.
+        let programmed = programmed(&config);
+        assert_eq!((programmed.rx_offset, programmed.rx_duration), (24.0, 21.0));
+        assert_eq!(programmed.rx_noise_offset + programmed.rx_noise_duration, 24.0);
+        let rx = crate::chronogram::windows(&config).into_iter().find(|w| w.name == crate::timing::RX).unwrap();
+        assert_eq!(rx.start(), 24.0);
+        let rules = |config: &RadarConfig| crate::timing::violations(config).iter().map(|v| v.rule).collect::<Vec<_>>();
+        assert!(rules(&RadarConfig { fech: 0.0, ..config.clone() }).contains(&"noise-overlaps-rx"));
+        assert!(!rules(&config).contains(&"noise-overlaps-rx"));
+
+        // Every PRI of a staggered sequence is on the sample clock.
+        let config = RadarConfig { pri_mode: PriMode::Staggered, pri_steps: 4, pri_stagger: 0.25, ..config };
+        assert_eq!(super::programmed(&config).pri_sequence(), [99.6, 99.9, 100.1, 100.4]);
+        let figure = crate::chronogram::figure(&config);
+        let second = figure.series.iter().find(|s| s.name == "TX (Ambiguity 1)").unwrap();
+        assert_eq!(second.points[0][0], 99.6);
+    }
+
+    #[test]
+    fn agility_plan_round_trip() {
+        let mut config = RadarConfig { fech: 100.0, center_frequency: 9.6, nb_agilities: 3, ..RadarConfig::default() };
+        config.resize_agilities();
+        config.agility_offsets = vec![0.0, 40.5, -40.5];
+        config.agility_sequence = vec![2, 0, 1];
+        let text = to_string(&config).unwrap();
+        let (imported, _) = from_bytes(text.as_bytes()).unwrap();
+        assert_eq!(imported.nb_agilities, 3);
+        assert_eq!(imported.agility_offsets, config.agility_offsets);
+        assert_eq!(imported.agility_sequence, config.agility_sequence);
+
+        let errors = from_bytes(text.replace("2,0,1", "2,2,1").as_bytes()).err().unwrap();
+        assert!(errors[0].ends_with("must list each of the 3 agility steps once"));
+    }
+}
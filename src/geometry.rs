@@ -1,80 +1,136 @@
-use eframe::egui::{Color32, Ui};
-use egui_plot::{Plot, Line, PlotPoints, Legend, Corner};
+// Side view of the acquisition geometry in the vertical plane of the line of
+// sight: the transmitter above its nadir, the ground, the elevation beam and
+// the ground points echoing at the edges of the RX window.
+use crate::figure::{self, Figure, Series, Style};
+use crate::{RadarConfig, bistatic, earth};
 
-fn arc_to_points(radius: f64, start_angle_deg: f64, end_angle_deg: f64, center: (f64, f64)) -> PlotPoints<'static> {
-    let points: Vec<[f64; 2]> = (0..=100)
+// Points of the ground at equal path length `range_sum` through the receiver,
+// seen from the transmitter between horizontal and the ground. In a monostatic
+// configuration this is the arc of radius `range_sum / 2`.
+fn iso_range_to_points(config: &RadarConfig, range_sum: f64) -> Vec<[f64; 2]> {
+    let height = config.carrier_height;
+    if range_sum <= bistatic::direct_path(config) {
+        return Vec::new();
+    }
+    let look_angle_deg = bistatic::look_angle_at_range_sum(config, range_sum);
+    let end_angle_deg = if !look_angle_deg.is_nan() {
+        90.0 - look_angle_deg
+    } else if range_sum <= bistatic::range_sum(config, 0.0) {
+        90.0
+    } else {
+        // Beyond the horizon
+        let radius = config.earth_radius();
+        90.0 - (radius / (radius + height)).asin().to_degrees()
+    };
+    (0..=100)
         .map(|i| {
-            let t = i as f64 / 100.0;
-            let angle = start_angle_deg.to_radians() + t * (end_angle_deg - start_angle_deg).to_radians();
-            [
-                center.0 + radius * angle.cos(),
-                center.1 - radius * angle.sin(),
-            ]
+            let angle_deg = end_angle_deg * i as f64 / 100.0;
+            let range = bistatic::iso_range(config, range_sum, angle_deg);
+            let angle = angle_deg.to_radians();
+            [range * angle.cos(), height - range * angle.sin()]
         })
-        .collect();
-    points.into()
+        .filter(|[x, y]| x.is_finite() && y.is_finite())
+        .collect()
 }
 
-fn arc_to_points_auto(radius: f64, center: (f64, f64)) -> PlotPoints<'static> {
-    let start_angle_deg = 0.0;    
-    if radius <= center.1 {
-        let end_angle_deg = 90.0;
-        return arc_to_points(radius, start_angle_deg, end_angle_deg, center.clone());
-    } else {
-        let end_angle_deg = 90.0 - (center.1 / radius).acos().to_degrees();
-        return arc_to_points(radius, start_angle_deg, end_angle_deg, center.clone());
-    }
+fn ground_point(config: &RadarConfig, look_angle_deg: f64) -> [f64; 2] {
+    earth::surface_point(config.earth_radius(), config.carrier_height, look_angle_deg)
 }
 
-pub fn plot(ui: &mut Ui, position: (f64, f64), look_angle_deg: f64, aperture_angles_deg: Option<(f64, f64)>, numerization_window: Option<(f64, f64)>) {
+// Height of the ground at the horizontal distance `x` from the transmitter nadir.
+fn ground_height(config: &RadarConfig, x: f64) -> f64 {
+    let radius = config.earth_radius();
+    if radius.is_infinite() { 0.0 } else { (radius * radius - x * x).sqrt() - radius }
+}
 
-    let nadir = Line::new(
-        "Nadir",
-        vec![[0.0, 0.0], [position.0, position.1]],
-    ).color(Color32::WHITE).style(egui_plot::LineStyle::Dashed { length: 5.0 });
+pub fn figure(config: &RadarConfig) -> Figure {
+    let height = config.carrier_height;
+    let (min_angle, max_angle) = config.aperture_angles();
 
-    let target = Line::new(
-        "Radar-Target",
-        vec![[position.0, position.1], [position.0 + position.1 * look_angle_deg.to_radians().tan(), 0.0]],
-    ).color(Color32::DARK_GREEN);
+    // Ground surface from nadir to a little beyond the farthest point drawn.
+    let far_x = ground_point(config, max_angle.max(config.look_angle))[0];
+    let far_x = if far_x.is_finite() { far_x } else { height };
+    let far_x = if config.bsar_config { far_x.max(config.rx_across_track_baseline) } else { far_x };
 
-    let legend = Legend::default().position(Corner::RightTop);
+    let mut series = vec![
+        Series {
+            name: "Ground".to_string(),
+            points: (0..=100)
+                .map(|i| {
+                    let x = 1.2 * far_x * i as f64 / 100.0;
+                    [x, ground_height(config, x)]
+                })
+                .collect(),
+            color: figure::GRAY,
+            ..Default::default()
+        },
+        Series {
+            name: "Nadir".to_string(),
+            points: vec![[0.0, 0.0], [0.0, height]],
+            color: figure::WHITE,
+            style: Style::Dashed,
+            ..Default::default()
+        },
+        Series {
+            name: "Radar-Target".to_string(),
+            points: vec![[0.0, height], ground_point(config, config.look_angle)],
+            color: figure::DARK_GREEN,
+            ..Default::default()
+        },
+        Series {
+            name: "Beamwidth".to_string(),
+            points: vec![ground_point(config, min_angle), [0.0, height], ground_point(config, max_angle)],
+            color: figure::BLUE,
+            ..Default::default()
+        },
+    ];
 
-    Plot::new("Geometry")
-        .data_aspect(1.0)
-        .height(300.0)
-        .legend(legend)
-        .x_axis_formatter(|x, _| format!("{:.1} m", x.value))
-        .y_axis_formatter(|y, _| format!("{:.1} m", y.value))
-        .show(ui, |plot_ui| {
-            plot_ui.line(nadir);
-            plot_ui.line(target);
+    // The receiver is drawn projected on the plane of the transmitter line of sight.
+    if config.bsar_config {
+        let [x, _, z] = bistatic::receiver(config);
+        series.push(Series {
+            name: "RX Nadir".to_string(),
+            points: vec![[x, ground_height(config, x)], [x, z]],
+            color: figure::LIGHT_GRAY,
+            style: Style::Dashed,
+            ..Default::default()
+        });
+        series.push(Series {
+            name: "Target-Receiver".to_string(),
+            points: vec![ground_point(config, config.look_angle), [x, z]],
+            color: figure::LIGHT_GREEN,
+            ..Default::default()
+        });
+        series.push(Series {
+            name: "Receiver".to_string(),
+            points: vec![[x, z]],
+            color: figure::LIGHT_GREEN,
+            marker: true,
+            ..Default::default()
+        });
+    }
 
-            if let Some(lobe) = aperture_angles_deg {
-                plot_ui.line(
-                    Line::new(
-                        "Beamwidth",
-                        vec![
-                            [position.0 + position.1 * lobe.0.to_radians().tan(), 0.0],
-                            [position.0, position.1],
-                            [position.0 + position.1 * lobe.1.to_radians().tan(), 0.0],
-                        ],
-                    ).color(Color32::BLUE)
-                );
-            }
+    let (start, end) = config.numerization_range_sums();
+    if start < end {
+        for (range_sum, style) in [(start, Style::Solid), (end, Style::Dashed)] {
+            series.push(Series {
+                name: "RX Window".to_string(),
+                points: iso_range_to_points(config, range_sum),
+                color: figure::YELLOW,
+                width: 2.0,
+                style,
+                ..Default::default()
+            });
+        }
+    }
 
-            if let Some((start, end)) = numerization_window && start < end {
-                plot_ui.line(
-                    Line::new("RX Window", arc_to_points_auto(start, position.clone()))
-                        .color(Color32::YELLOW)
-                        .width(2.0)
-                );
-                plot_ui.line(
-                    Line::new("RX Window", arc_to_points_auto(end, position.clone()))
-                        .style(egui_plot::LineStyle::Dashed { length: 5.0 })
-                        .color(Color32::YELLOW)
-                        .width(2.0)
-                );
-            }
-        });
+    Figure {
+        title: format!("{} - Geometry", config.config_name),
+        x_unit: "m",
+        y_unit: "m",
+        equal_aspect: true,
+        size: [1200, 800],
+        series,
+        ..Default::default()
+    }
 }
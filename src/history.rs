@@ -0,0 +1,137 @@
+use serde_json::Value;
+use sarconf::{RadarConfig, json};
+
+const MAX_ENTRIES: usize = 100;
+
+pub struct Entry {
+    pub label: String,
+    pub config: RadarConfig,
+}
+
+// Snapshots of the configuration after each edit. Undoing moves back in the
+// list, and a new edit drops the snapshots that were undone.
+pub struct History {
+    entries: Vec<Entry>,
+    current: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            entries: vec![Entry { label: "Initial configuration".to_string(), config: RadarConfig::default() }],
+            current: 0,
+        }
+    }
+}
+
+// Parameters differing between two configurations, as `section.key` paths of the JSON document.
+fn changes(before: &RadarConfig, after: &RadarConfig) -> Vec<String> {
+    let (before, after) = (json::to_value(before), json::to_value(after));
+    let (Value::Object(before), Value::Object(after)) = (before, after) else {
+        return Vec::new();
+    };
+    let mut paths = Vec::new();
+    for (key, value) in &after {
+        match (before.get(key), value) {
+            (Some(Value::Object(old)), Value::Object(new)) => {
+                for (name, quantity) in new {
+                    if old.get(name) != Some(quantity) {
+                        paths.push(format!("{}.{}", key, name));
+                    }
+                }
+            }
+            (old, new) if old != Some(new) => paths.push(key.clone()),
+            _ => {}
+        }
+    }
+    paths
+}
+
+impl History {
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.current > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.current + 1 < self.entries.len()
+    }
+
+    pub fn push(&mut self, label: String, config: &RadarConfig) {
+        self.entries.truncate(self.current + 1);
+        self.entries.push(Entry { label, config: config.clone() });
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.current = self.entries.len() - 1;
+    }
+
+    // Adds `config` if it differs from the current snapshot, labelled with the edited parameters.
+    pub fn record(&mut self, config: &RadarConfig) {
+        let previous = &self.entries[self.current].config;
+        if previous == config {
+            return;
+        }
+        let paths = changes(previous, config);
+        let label = match paths.len() {
+            0 => "Edit".to_string(),
+            1 => paths[0].clone(),
+            n => format!("{} and {} more", paths[0], n - 1),
+        };
+        self.push(label, config);
+    }
+
+    pub fn undo(&mut self) -> Option<&RadarConfig> {
+        self.can_undo().then(|| self.jump(self.current - 1))
+    }
+
+    pub fn redo(&mut self) -> Option<&RadarConfig> {
+        self.can_redo().then(|| self.jump(self.current + 1))
+    }
+
+    pub fn jump(&mut self, index: usize) -> &RadarConfig {
+        self.current = index.min(self.entries.len() - 1);
+        &self.entries[self.current].config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_redo() {
+        let mut history = History::default();
+        let mut config = RadarConfig::default();
+        history.record(&config);
+        assert!(!history.can_undo());
+
+        config.carrier_height = 5000.0;
+        history.record(&config);
+        config.pri = 200.0;
+        config.tx_duration = 20.0;
+        history.record(&config);
+        assert_eq!(history.entries()[1].label, "carrier.height");
+        assert_eq!(history.entries()[2].label, "tx.pri and 1 more");
+
+        assert_eq!(history.undo().unwrap().pri, 100.0);
+        assert_eq!(history.undo().unwrap().carrier_height, 3000.0);
+        assert!(history.undo().is_none());
+        assert_eq!(history.redo().unwrap().carrier_height, 5000.0);
+
+        // A new edit drops the undone snapshots.
+        config = history.entries()[history.current()].config.clone();
+        config.look_angle = 30.0;
+        history.record(&config);
+        assert!(!history.can_redo());
+        assert_eq!(history.entries().len(), 3);
+        assert_eq!(history.jump(0).look_angle, 45.0);
+    }
+}
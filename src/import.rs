@@ -0,0 +1,120 @@
+use crate::config::RadarConfig;
+use crate::json::FIELDS;
+
+pub type Imported = Result<(RadarConfig, Vec<String>), Vec<String>>;
+
+// Links a key of a foreign file format to a configuration field (see `json::FIELDS`),
+// `scale` converting the file unit into the unit used by SARConf.
+pub struct Mapping {
+    pub key: &'static str,
+    pub field: &'static str,
+    pub scale: f64,
+}
+
+pub fn text(bytes: &[u8]) -> Result<&str, Vec<String>> {
+    std::str::from_utf8(bytes).map_err(|err| vec![format!("not a text file: {}", err)])
+}
+
+// Builds a configuration from the `(key, value)` entries read from a file, warning
+// about the keys SARConf does not model and the parameters the file did not provide.
+pub fn apply(format: &str, name: Option<String>, entries: Vec<(String, f64)>, mappings: &[Mapping]) -> Imported {
+    let mut config = RadarConfig::default();
+    if let Some(name) = name {
+        config.config_name = name;
+    }
+
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let mut provided = Vec::new();
+    for (key, value) in entries {
+        let Some(mapping) = mappings.iter().find(|m| m.key == key) else {
+            warnings.push(format!("{}: not modelled by SARConf, ignored", key));
+            continue;
+        };
+        let field = FIELDS.iter().find(|f| f.path() == mapping.field).unwrap();
+        // Divide by exact powers of ten rather than multiplying by their inexact
+        // inverse, so that 9.6e9 Hz reads back as 9.6 GHz and not 9.600000000000001.
+        let value = if mapping.scale < 1.0 {
+            value / (1.0 / mapping.scale).round()
+        } else {
+            value * mapping.scale
+        };
+        match field.check(value) {
+            Ok(value) => {
+                (field.set)(&mut config, value);
+                provided.push(mapping.field);
+            }
+            Err(err) => errors.push(format!("{}: {}", key, err)),
+        }
+    }
+    for field in FIELDS {
+        let path = field.path();
+        if !provided.contains(&path.as_str()) {
+            warnings.push(format!("{}: not expressed in the {} file, default value kept", path, format));
+        }
+    }
+
+    config.resize_agilities();
+    config.resize_channels();
+
+    if errors.is_empty() { Ok((config, warnings)) } else { Err(errors) }
+}
+
+// Agility steps of a `2,0,1` sequence.
+pub fn parse_sequence(value: &str) -> Option<Vec<u32>> {
+    value.split(',').map(|step| step.trim().parse().ok()).collect()
+}
+
+// Sets the frequency offsets of the agility steps, given as `(key, step, offset in MHz)`,
+// and their sequence read from the file under `key`, once `apply` knows the number of steps.
+pub fn apply_agilities(
+    config: &mut RadarConfig,
+    warnings: &mut Vec<String>,
+    offsets: Vec<(String, usize, f64)>,
+    sequence: Option<(&str, Vec<u32>)>,
+) -> Result<(), Vec<String>> {
+    for (key, step, offset) in offsets {
+        match config.agility_offsets.get_mut(step) {
+            Some(value) => *value = offset,
+            None => warnings.push(format!("{}: no such agility step, ignored", key)),
+        }
+    }
+    if let Some((key, sequence)) = sequence {
+        if !config.is_agility_sequence(&sequence) {
+            return Err(vec![format!("{}: must list each of the {} agility steps once", key, config.nb_agilities)]);
+        }
+        config.agility_sequence = sequence;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPPINGS: &[Mapping] = &[
+        Mapping { key: "PRI_S", field: "tx.pri", scale: 1e6 },
+        Mapping { key: "CHANNELS", field: "rx.nb_channels", scale: 1.0 },
+        Mapping { key: "AGILITIES", field: "tx.nb_agilities", scale: 1.0 },
+    ];
+
+    #[test]
+    fn mapped_entries() {
+        let entries = vec![("PRI_S".to_string(), 2e-4), ("CHANNELS".to_string(), 4.0), ("GAIN".to_string(), 3.0)];
+        let (config, warnings) = apply("Test", Some("Imported".to_string()), entries, MAPPINGS).unwrap();
+        assert_eq!((config.config_name.as_str(), config.pri), ("Imported", 200.0));
+        assert_eq!(config.channels.len(), 4);
+        assert_eq!(warnings[0], "GAIN: not modelled by SARConf, ignored");
+        assert!(warnings.contains(&"carrier.height: not expressed in the Test file, default value kept".to_string()));
+        assert!(!warnings.iter().any(|w| w.starts_with("tx.pri:")));
+
+        // Counts are checked before the per-step and per-channel parameters are allocated.
+        let entries = vec![("CHANNELS".to_string(), 4e9), ("AGILITIES".to_string(), 2.5), ("PRI_S".to_string(), 0.0)];
+        let errors = apply("Test", None, entries, MAPPINGS).err().unwrap();
+        assert_eq!(errors, [
+            "CHANNELS: 4000000000 count is out of range [1, 64]",
+            "AGILITIES: 2.5 is not an integer",
+            "PRI_S: 0 us is out of range [1, inf]",
+        ]);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::config::RadarConfig;
+
+// 1 when a single transmitter illuminates both receivers (bistatic single-pass),
+// 2 when each antenna receives its own transmission (monostatic repeat-pass or ping-pong).
+pub fn path_factor(config: &RadarConfig) -> f64 {
+    if config.bsar_config { 1.0 } else { 2.0 }
+}
+
+// Height difference producing a 2π interferometric phase change, in m.
+pub fn height_of_ambiguity(config: &RadarConfig, look_angle_deg: f64, baseline_m: f64) -> f64 {
+    let range = config.slant_range_at(look_angle_deg);
+    config.wavelength() * range * config.incidence_angle(look_angle_deg).to_radians().sin() / (path_factor(config) * baseline_m)
+}
+
+// Perpendicular baseline giving the height of ambiguity `height_ambiguity_m`, in m.
+pub fn required_baseline(config: &RadarConfig, look_angle_deg: f64, height_ambiguity_m: f64) -> f64 {
+    let range = config.slant_range_at(look_angle_deg);
+    config.wavelength() * range * config.incidence_angle(look_angle_deg).to_radians().sin() / (path_factor(config) * height_ambiguity_m)
+}
+
+// Standard deviation of the height for a phase noise of `phase_noise_deg`, in m.
+pub fn height_accuracy(height_ambiguity_m: f64, phase_noise_deg: f64) -> f64 {
+    height_ambiguity_m * phase_noise_deg / 360.0
+}
+
+// Required baseline across the swath, as `[ground range (m), baseline (m)]` points.
+pub fn required_baseline_profile(config: &RadarConfig, nb_points: usize) -> Vec<[f64; 2]> {
+    config.swath_angles(nb_points)
+        .into_iter()
+        .map(|angle| [config.ground_range(angle), required_baseline(config, angle, config.height_ambiguity)])
+        .filter(|[_, baseline]| baseline.is_finite())
+        .collect()
+}
+
+// Height of ambiguity across the swath for the configured perpendicular baseline,
+// as `[ground range (m), height of ambiguity (m)]` points.
+pub fn height_of_ambiguity_profile(config: &RadarConfig, nb_points: usize) -> Vec<[f64; 2]> {
+    config.swath_angles(nb_points)
+        .into_iter()
+        .map(|angle| [config.ground_range(angle), height_of_ambiguity(config, angle, config.perpendicular_baseline)])
+        .filter(|[_, height]| height.is_finite())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn baseline_and_height_of_ambiguity_are_reciprocal() {
+        let config = RadarConfig { center_frequency: 9.6, ..RadarConfig::default() };
+        let baseline = required_baseline(&config, 45.0, 20.0);
+        assert!((height_of_ambiguity(&config, 45.0, baseline) - 20.0).abs() < 1e-9);
+
+        // Monostatic repeat-pass needs half the baseline of a bistatic single-pass system.
+        let bistatic = RadarConfig { bsar_config: true, ..config.clone() };
+        assert!((required_baseline(&bistatic, 45.0, 20.0) - 2.0 * baseline).abs() < 1e-9);
+        assert_eq!(height_accuracy(20.0, 36.0), 2.0);
+    }
+}
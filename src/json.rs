@@ -0,0 +1,590 @@
+use serde_json::{json, Map, Value};
+use crate::config::{Channel, MAX_AGILITIES, MAX_CHANNELS, MAX_PRI_STEPS, MIN_PRI, RadarConfig};
+use crate::earth::EarthModel;
+use crate::import::{self, Imported};
+use crate::pri::PriMode;
+
+pub const SCHEMA_VERSION: u64 = 1;
+
+pub struct Field {
+    pub section: &'static str,
+    pub key: &'static str,
+    pub unit: &'static str,
+    pub min: f64,
+    pub max: f64,
+    pub integer: bool,
+    pub get: fn(&RadarConfig) -> f64,
+    pub set: fn(&mut RadarConfig, f64),
+}
+
+impl Field {
+    pub fn path(&self) -> String {
+        format!("{}.{}", self.section, self.key)
+    }
+
+    pub fn check(&self, value: f64) -> Result<f64, String> {
+        if !(self.min..=self.max).contains(&value) {
+            return Err(format!("{} {} is out of range [{}, {}]", value, self.unit, self.min, self.max));
+        }
+        if self.integer && value.fract() != 0.0 {
+            return Err(format!("{} is not an integer", value));
+        }
+        Ok(value)
+    }
+}
+
+// Every numeric parameter of the configuration, in the order they are written to the file.
+pub const FIELDS: &[Field] = &[
+    Field { section: "carrier", key: "height", unit: "m", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.carrier_height, set: |config, v| config.carrier_height = v },
+    Field { section: "carrier", key: "velocity", unit: "m/s", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.carrier_velocity, set: |config, v| config.carrier_velocity = v },
+    Field { section: "carrier", key: "look_angle", unit: "deg", min: 0.0, max: 90.0, integer: false,
+        get: |config| config.look_angle, set: |config, v| config.look_angle = v },
+    Field { section: "carrier", key: "latitude", unit: "deg", min: -90.0, max: 90.0, integer: false,
+        get: |config| config.latitude, set: |config, v| config.latitude = v },
+    Field { section: "receiver", key: "height", unit: "m", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.rx_carrier_height, set: |config, v| config.rx_carrier_height = v },
+    Field { section: "receiver", key: "velocity", unit: "m/s", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.rx_carrier_velocity, set: |config, v| config.rx_carrier_velocity = v },
+    Field { section: "receiver", key: "look_angle", unit: "deg", min: 0.0, max: 90.0, integer: false,
+        get: |config| config.rx_look_angle, set: |config, v| config.rx_look_angle = v },
+    Field { section: "receiver", key: "along_track_baseline", unit: "m", min: f64::NEG_INFINITY, max: f64::INFINITY, integer: false,
+        get: |config| config.rx_along_track_baseline, set: |config, v| config.rx_along_track_baseline = v },
+    Field { section: "receiver", key: "across_track_baseline", unit: "m", min: f64::NEG_INFINITY, max: f64::INFINITY, integer: false,
+        get: |config| config.rx_across_track_baseline, set: |config, v| config.rx_across_track_baseline = v },
+    Field { section: "antenna", key: "elevation_aperture_angle", unit: "deg", min: 0.0, max: 360.0, integer: false,
+        get: |config| config.elevation_aperture_angle, set: |config, v| config.elevation_aperture_angle = v },
+    Field { section: "antenna", key: "azimuth_aperture_angle", unit: "deg", min: 0.0, max: 360.0, integer: false,
+        get: |config| config.azimuth_aperture_angle, set: |config, v| config.azimuth_aperture_angle = v },
+    Field { section: "tx", key: "nb_agilities", unit: "count", min: 1.0, max: MAX_AGILITIES as f64, integer: true,
+        get: |config| config.nb_agilities as f64, set: |config, v| config.nb_agilities = v as u32 },
+    Field { section: "tx", key: "pri", unit: "us", min: MIN_PRI, max: f64::INFINITY, integer: false,
+        get: |config| config.pri, set: |config, v| config.pri = v },
+    Field { section: "tx", key: "pri_steps", unit: "count", min: 1.0, max: MAX_PRI_STEPS as f64, integer: true,
+        get: |config| config.pri_steps as f64, set: |config, v| config.pri_steps = v as u32 },
+    Field { section: "tx", key: "pri_stagger", unit: "us", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.pri_stagger, set: |config, v| config.pri_stagger = v },
+    Field { section: "tx", key: "pri_jitter", unit: "us", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.pri_jitter, set: |config, v| config.pri_jitter = v },
+    Field { section: "tx", key: "offset", unit: "us", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.tx_offset, set: |config, v| config.tx_offset = v },
+    Field { section: "tx", key: "duration", unit: "us", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.tx_duration, set: |config, v| config.tx_duration = v },
+    Field { section: "rx", key: "fech", unit: "MHz", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.fech, set: |config, v| config.fech = v },
+    Field { section: "rx", key: "offset", unit: "us", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.rx_offset, set: |config, v| config.rx_offset = v },
+    Field { section: "rx", key: "duration", unit: "us", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.rx_duration, set: |config, v| config.rx_duration = v },
+    Field { section: "rx", key: "noise_offset", unit: "us", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.rx_noise_offset, set: |config, v| config.rx_noise_offset = v },
+    Field { section: "rx", key: "noise_duration", unit: "us", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.rx_noise_duration, set: |config, v| config.rx_noise_duration = v },
+    Field { section: "rx", key: "reinj_offset", unit: "us", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.rx_reinj_offset, set: |config, v| config.rx_reinj_offset = v },
+    Field { section: "rx", key: "reinj_duration", unit: "us", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.rx_reinj_duration, set: |config, v| config.rx_reinj_duration = v },
+    Field { section: "rx", key: "margin", unit: "us", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.rx_margin, set: |config, v| config.rx_margin = v },
+    Field { section: "rx", key: "nb_channels", unit: "count", min: 1.0, max: MAX_CHANNELS as f64, integer: true,
+        get: |config| config.nb_channels as f64, set: |config, v| config.nb_channels = v as u32 },
+    Field { section: "sensitivity", key: "peak_power", unit: "W", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.peak_power, set: |config, v| config.peak_power = v },
+    Field { section: "sensitivity", key: "loss_power", unit: "dB", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.loss_power, set: |config, v| config.loss_power = v },
+    Field { section: "sensitivity", key: "gain_antenna", unit: "dB", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.gain_antenna, set: |config, v| config.gain_antenna = v },
+    Field { section: "sensitivity", key: "noise_factor", unit: "dB", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.noise_factor, set: |config, v| config.noise_factor = v },
+    Field { section: "sensitivity", key: "center_frequency", unit: "GHz", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.center_frequency, set: |config, v| config.center_frequency = v },
+    Field { section: "sensitivity", key: "bandwidth", unit: "MHz", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.bandwidth, set: |config, v| config.bandwidth = v },
+    Field { section: "processing", key: "integration_length", unit: "m", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.integration_length, set: |config, v| config.integration_length = v },
+    Field { section: "level", key: "retrodiff", unit: "dB", min: f64::NEG_INFINITY, max: f64::INFINITY, integer: false,
+        get: |config| config.retrodiff, set: |config, v| config.retrodiff = v },
+    Field { section: "level", key: "rx_gain", unit: "dB", min: f64::NEG_INFINITY, max: f64::INFINITY, integer: false,
+        get: |config| config.rx_gain, set: |config, v| config.rx_gain = v },
+    Field { section: "level", key: "adc_full_scale", unit: "dBm", min: f64::NEG_INFINITY, max: f64::INFINITY, integer: false,
+        get: |config| config.adc_full_scale, set: |config, v| config.adc_full_scale = v },
+    Field { section: "level", key: "adc_noise_floor", unit: "dBm", min: f64::NEG_INFINITY, max: f64::INFINITY, integer: false,
+        get: |config| config.adc_noise_floor, set: |config, v| config.adc_noise_floor = v },
+    Field { section: "interferometry", key: "height_ambiguity", unit: "m", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.height_ambiguity, set: |config, v| config.height_ambiguity = v },
+    Field { section: "interferometry", key: "accuracy_height_ambiguity", unit: "m", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.accuracy_height_ambiguity, set: |config, v| config.accuracy_height_ambiguity = v },
+    Field { section: "interferometry", key: "perpendicular_baseline", unit: "m", min: 0.0, max: f64::INFINITY, integer: false,
+        get: |config| config.perpendicular_baseline, set: |config, v| config.perpendicular_baseline = v },
+    Field { section: "interferometry", key: "phase_noise", unit: "deg", min: 0.0, max: 180.0, integer: false,
+        get: |config| config.phase_noise, set: |config, v| config.phase_noise = v },
+];
+
+pub fn to_value(config: &RadarConfig) -> Value {
+    let mut doc = Map::new();
+    doc.insert("schema_version".to_string(), json!(SCHEMA_VERSION));
+    doc.insert("config_name".to_string(), json!(config.config_name));
+    doc.insert("bistatic".to_string(), json!(config.bsar_config));
+    doc.insert("earth_model".to_string(), json!(config.earth_model.key()));
+    for field in FIELDS {
+        let value = (field.get)(config);
+        let value = if field.integer { json!(value as u64) } else { json!(value) };
+        insert_quantity(&mut doc, field.section, field.key, value, field.unit);
+    }
+    insert_quantity(&mut doc, "tx", "agility_offsets", json!(config.agility_offsets), "MHz");
+    doc["tx"].as_object_mut().unwrap().insert("agility_sequence".to_string(), json!(config.agility_sequence));
+    doc["tx"].as_object_mut().unwrap().insert("pri_mode".to_string(), json!(config.pri_mode.key()));
+    insert_quantity(&mut doc, "tx", "pri_list", json!(config.pri_list), "us");
+    let channels: Vec<Value> = config.channels.iter().map(channel_to_value).collect();
+    doc["rx"].as_object_mut().unwrap().insert("channels".to_string(), json!(channels));
+    Value::Object(doc)
+}
+
+fn channel_to_value(channel: &Channel) -> Value {
+    json!({
+        "position": { "value": channel.position, "unit": "m" },
+        "cable_delay": { "value": channel.cable_delay, "unit": "ns" },
+        "gain": { "value": channel.gain, "unit": "dB" },
+        "enabled": channel.enabled,
+    })
+}
+
+fn insert_quantity(doc: &mut Map<String, Value>, section: &str, key: &str, value: Value, unit: &str) {
+    doc.entry(section)
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .unwrap()
+        .insert(key.to_string(), json!({ "value": value, "unit": unit }));
+}
+
+pub fn to_string(config: &RadarConfig) -> String {
+    serde_json::to_string_pretty(&to_value(config)).unwrap() + "\n"
+}
+
+// Documents written before the schema was versioned are a flat dump of the
+// application fields, with values implicitly expressed in the units shown in the UI.
+const V0_KEYS: &[(&str, &str, &str)] = &[
+    ("carrier_height", "carrier", "height"),
+    ("carrier_velocity", "carrier", "velocity"),
+    ("look_angle", "carrier", "look_angle"),
+    ("elevation_aperture_angle", "antenna", "elevation_aperture_angle"),
+    ("azimuth_aperture_angle", "antenna", "azimuth_aperture_angle"),
+    ("nb_agilities", "tx", "nb_agilities"),
+    ("pri", "tx", "pri"),
+    ("tx_offset", "tx", "offset"),
+    ("tx_duration", "tx", "duration"),
+    ("fech", "rx", "fech"),
+    ("rx_offset", "rx", "offset"),
+    ("rx_duration", "rx", "duration"),
+    ("rx_noise_offset", "rx", "noise_offset"),
+    ("rx_noise_duration", "rx", "noise_duration"),
+    ("rx_reinj_offset", "rx", "reinj_offset"),
+    ("rx_reinj_duration", "rx", "reinj_duration"),
+    ("nb_channels", "rx", "nb_channels"),
+    ("peak_power", "sensitivity", "peak_power"),
+    ("loss_power", "sensitivity", "loss_power"),
+    ("gain_antenna", "sensitivity", "gain_antenna"),
+    ("noise_factor", "sensitivity", "noise_factor"),
+    ("center_frequency", "sensitivity", "center_frequency"),
+    ("bandwidth", "sensitivity", "bandwidth"),
+    ("retrodiff", "level", "retrodiff"),
+    ("rx_gain", "level", "rx_gain"),
+    ("height_ambiguity", "interferometry", "height_ambiguity"),
+    ("accuracy_height_ambiguity", "interferometry", "accuracy_height_ambiguity"),
+];
+
+// Count `key` of `section` in a migrated document, when it passes the check of its field.
+fn migrated_count(doc: &Map<String, Value>, section: &str, key: &str) -> Option<usize> {
+    let field = FIELDS.iter().find(|f| f.section == section && f.key == key).unwrap();
+    let value = doc.get(section)?.get(key)?.get("value")?.as_f64()?;
+    field.check(value).ok().map(|count| count as usize)
+}
+
+// Every parameter missing from these documents keeps its default value.
+fn migrate_v0(mut doc: Map<String, Value>) -> Map<String, Value> {
+    let defaults = to_value(&RadarConfig::default());
+    let mut migrated = Map::new();
+    migrated.insert("config_name".to_string(), doc.remove("config_name").unwrap_or_else(|| defaults["config_name"].clone()));
+    migrated.insert("bistatic".to_string(), doc.remove("bsar_config").unwrap_or_else(|| defaults["bistatic"].clone()));
+    for (old_key, section, key) in V0_KEYS {
+        let value = doc.remove(*old_key).unwrap_or_else(|| defaults[section][key]["value"].clone());
+        insert_quantity(&mut migrated, section, key, value, defaults[section][key]["unit"].as_str().unwrap());
+    }
+    migrated
+}
+
+// Entries written by the first version of the schema, the later parameters having
+// been added without changing the version.
+const FIRST_VERSION_ENTRIES: &[&str] = &["schema_version", "config_name", "bistatic"];
+
+fn in_first_version(section: &str, key: &str) -> bool {
+    V0_KEYS.iter().any(|(_, s, k)| (*s, *k) == (section, key))
+}
+
+// Adds the parameters introduced after the first version of the schema that the
+// document lacks, warning about each of them. They keep their default value, except
+// the receiver, which used to share the carrier of the transmitter, the PRI list,
+// which holds the single PRI, and the agility offsets and channels, one per count.
+fn complete(doc: &mut Map<String, Value>, warnings: &mut Vec<String>) {
+    let Value::Object(defaults) = to_value(&RadarConfig::default()) else {
+        unreachable!();
+    };
+    for (name, default) in defaults {
+        let Value::Object(entries) = default else {
+            if !doc.contains_key(&name) && !FIRST_VERSION_ENTRIES.contains(&name.as_str()) {
+                warnings.push(format!("{}: not in the file, default value kept", name));
+                doc.insert(name, default);
+            }
+            continue;
+        };
+        if doc.get(&name).is_some_and(|section| !section.is_object()) {
+            continue;
+        }
+        for (key, default) in entries {
+            if in_first_version(&name, &key) || doc.get(&name).is_some_and(|section| section.get(&key).is_some()) {
+                continue;
+            }
+            let carrier = doc.get("carrier").and_then(|carrier| carrier.get(&key)).cloned();
+            let pri = doc.get("tx").and_then(|tx| tx.get("pri")).and_then(|pri| pri.get("value")).cloned();
+            let value = match (name.as_str(), key.as_str()) {
+                ("receiver", _) => carrier.map(|value| (value, "carrier value")),
+                ("tx", "pri_list") => pri.map(|pri| (json!({ "value": [pri], "unit": "us" }), "single PRI")),
+                ("tx", "agility_offsets") => migrated_count(doc, "tx", "nb_agilities")
+                    .map(|nb_agilities| (json!({ "value": vec![0.0; nb_agilities], "unit": "MHz" }), "default value")),
+                ("rx", "channels") => migrated_count(doc, "rx", "nb_channels")
+                    .map(|nb_channels| (json!(vec![channel_to_value(&Channel::default()); nb_channels]), "default value")),
+                _ => None,
+            };
+            let (value, kept) = value.unwrap_or((default, "default value"));
+            warnings.push(format!("{}.{}: not in the file, {} kept", name, key, kept));
+            doc.entry(name.clone()).or_insert_with(|| json!({}))[&key] = value;
+        }
+    }
+}
+
+// Value of the `{ "value": ..., "unit": ... }` quantity `key` of a channel, checking its unit.
+fn channel_quantity<'a>(channel: &'a Value, path: &str, key: &str, unit: &str) -> Result<&'a Value, String> {
+    let quantity = channel.get(key).ok_or_else(|| format!("{}.{}: missing", path, key))?;
+    if quantity.get("unit").and_then(Value::as_str) != Some(unit) {
+        return Err(format!("{}.{}: expected unit \"{}\"", path, key, unit));
+    }
+    quantity.get("value").ok_or_else(|| format!("{}.{}: missing \"value\"", path, key))
+}
+
+fn read_channel(channel: &Value, path: &str) -> Result<Channel, String> {
+    let number = |key, unit| {
+        channel_quantity(channel, path, key, unit)?
+            .as_f64()
+            .ok_or_else(|| format!("{}.{}: \"value\" is not a number", path, key))
+    };
+    let position = channel_quantity(channel, path, "position", "m")?
+        .as_array()
+        .and_then(|p| p.iter().map(Value::as_f64).collect::<Option<Vec<f64>>>())
+        .and_then(|p| <[f64; 3]>::try_from(p).ok())
+        .ok_or_else(|| format!("{}.position: \"value\" is not a list of 3 numbers", path))?;
+    Ok(Channel {
+        position,
+        cable_delay: number("cable_delay", "ns")?,
+        gain: number("gain", "dB")?,
+        enabled: channel.get("enabled")
+            .and_then(Value::as_bool)
+            .ok_or_else(|| format!("{}.enabled: missing or not a boolean", path))?,
+    })
+}
+
+fn read_channels(doc: &Map<String, Value>, config: &mut RadarConfig) -> Result<(), Vec<String>> {
+    let channels = doc.get("rx")
+        .and_then(|rx| rx.get("channels"))
+        .ok_or_else(|| vec!["rx.channels: missing".to_string()])?
+        .as_array()
+        .ok_or_else(|| vec!["rx.channels: not an array".to_string()])?;
+    if channels.len() != config.nb_channels as usize {
+        return Err(vec![format!("rx.channels: {} channels for {} receiver channels", channels.len(), config.nb_channels)]);
+    }
+    let mut errors = Vec::new();
+    config.channels = channels.iter()
+        .enumerate()
+        .filter_map(|(i, channel)| read_channel(channel, &format!("rx.channels[{}]", i)).map_err(|err| errors.push(err)).ok())
+        .collect();
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+fn read_pri_sequence(doc: &Map<String, Value>, config: &mut RadarConfig) -> Result<(), Vec<String>> {
+    let tx = doc.get("tx");
+    let mut errors = Vec::new();
+    match tx.and_then(|tx| tx.get("pri_mode")).map(Value::as_str) {
+        Some(Some(key)) => match PriMode::from_key(key) {
+            Some(mode) => config.pri_mode = mode,
+            None => errors.push(format!("tx.pri_mode: unknown mode \"{}\"", key)),
+        },
+        Some(None) => errors.push("tx.pri_mode: not a string".to_string()),
+        None => errors.push("tx.pri_mode: missing".to_string()),
+    }
+    let list = tx.and_then(|tx| tx.get("pri_list"));
+    match list.and_then(|list| list.get("unit")).and_then(Value::as_str) {
+        Some("us") => {}
+        _ => errors.push("tx.pri_list: expected unit \"us\"".to_string()),
+    }
+    match list.and_then(|list| list.get("value")).and_then(Value::as_array) {
+        Some(values) => match values.iter().map(Value::as_f64).collect::<Option<Vec<f64>>>() {
+            Some(pris) if pris.len() > MAX_PRI_STEPS as usize => {
+                errors.push(format!("tx.pri_list: {} PRIs, more than the {} of a sequence", pris.len(), MAX_PRI_STEPS));
+            }
+            Some(pris) if !pris.is_empty() && pris.iter().all(|&pri| pri >= MIN_PRI) => config.pri_list = pris,
+            _ => errors.push(format!("tx.pri_list: expected a non-empty list of PRIs of at least {} us", MIN_PRI)),
+        },
+        None => errors.push("tx.pri_list: \"value\" is missing or not an array".to_string()),
+    }
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+fn read_agilities(doc: &Map<String, Value>, config: &mut RadarConfig) -> Result<(), String> {
+    let tx = doc.get("tx");
+    let offsets = tx.and_then(|tx| tx.get("agility_offsets")).ok_or("tx.agility_offsets: missing")?;
+    let unit = offsets.get("unit").and_then(Value::as_str);
+    if unit != Some("MHz") {
+        return Err("tx.agility_offsets: expected unit \"MHz\"".to_string());
+    }
+    config.agility_offsets = offsets.get("value")
+        .and_then(Value::as_array)
+        .ok_or("tx.agility_offsets: \"value\" is not an array")?
+        .iter()
+        .map(Value::as_f64)
+        .collect::<Option<_>>()
+        .ok_or("tx.agility_offsets: not a list of numbers")?;
+    if config.agility_offsets.len() != config.nb_agilities as usize {
+        return Err(format!(
+            "tx.agility_offsets: {} offsets for {} agility steps",
+            config.agility_offsets.len(), config.nb_agilities
+        ));
+    }
+
+    config.agility_sequence = tx.and_then(|tx| tx.get("agility_sequence"))
+        .ok_or("tx.agility_sequence: missing")?
+        .as_array()
+        .ok_or("tx.agility_sequence: not an array")?
+        .iter()
+        .map(|step| step.as_u64().and_then(|step| u32::try_from(step).ok()))
+        .collect::<Option<_>>()
+        .ok_or("tx.agility_sequence: not a list of agility steps")?;
+    if !config.agility_sequence.is_empty() && !config.is_agility_sequence(&config.agility_sequence) {
+        return Err(format!("tx.agility_sequence: must list each of the {} agility steps once", config.nb_agilities));
+    }
+    Ok(())
+}
+
+fn read_field(doc: &Map<String, Value>, field: &Field) -> Result<f64, String> {
+    let path = field.path();
+    let entry = doc.get(field.section)
+        .and_then(|section| section.get(field.key))
+        .ok_or_else(|| format!("{}: missing", path))?;
+    let value = entry.get("value")
+        .ok_or_else(|| format!("{}: missing \"value\"", path))?
+        .as_f64()
+        .ok_or_else(|| format!("{}: \"value\" is not a number", path))?;
+    let unit = entry.get("unit")
+        .ok_or_else(|| format!("{}: missing \"unit\"", path))?
+        .as_str()
+        .ok_or_else(|| format!("{}: \"unit\" is not a string", path))?;
+    if unit != field.unit {
+        return Err(format!("{}: expected unit \"{}\", found \"{}\"", path, field.unit, unit));
+    }
+    field.check(value).map_err(|err| format!("{}: {}", path, err))
+}
+
+pub fn from_str(text: &str) -> Imported {
+    let mut doc = match serde_json::from_str(text) {
+        Ok(Value::Object(doc)) => doc,
+        Ok(_) => return Err(vec!["the document is not a JSON object".to_string()]),
+        Err(err) => return Err(vec![format!("invalid JSON: {}", err)]),
+    };
+
+    let version = match doc.get("schema_version") {
+        None => 0,
+        Some(v) => v.as_u64().ok_or_else(|| vec!["schema_version: not an unsigned integer".to_string()])?,
+    };
+    if version > SCHEMA_VERSION {
+        return Err(vec![format!(
+            "schema_version: version {} is newer than the supported version {}",
+            version, SCHEMA_VERSION
+        )]);
+    }
+    if version < 1 {
+        doc = migrate_v0(doc);
+    }
+    let mut warnings = Vec::new();
+    complete(&mut doc, &mut warnings);
+
+    let mut config = RadarConfig::default();
+    let mut errors = Vec::new();
+    match doc.get("config_name").map(Value::as_str) {
+        Some(Some(name)) => config.config_name = name.to_string(),
+        Some(None) => errors.push("config_name: not a string".to_string()),
+        None => errors.push("config_name: missing".to_string()),
+    }
+    match doc.get("bistatic").map(Value::as_bool) {
+        Some(Some(bistatic)) => config.bsar_config = bistatic,
+        Some(None) => errors.push("bistatic: not a boolean".to_string()),
+        None => errors.push("bistatic: missing".to_string()),
+    }
+    match doc.get("earth_model").map(Value::as_str) {
+        Some(Some(key)) => match EarthModel::from_key(key) {
+            Some(model) => config.earth_model = model,
+            None => errors.push(format!("earth_model: unknown model \"{}\"", key)),
+        },
+        Some(None) => errors.push("earth_model: not a string".to_string()),
+        None => errors.push("earth_model: missing".to_string()),
+    }
+    for field in FIELDS {
+        match read_field(&doc, field) {
+            Ok(value) => (field.set)(&mut config, value),
+            Err(err) => errors.push(err),
+        }
+    }
+    if let Err(err) = read_agilities(&doc, &mut config) {
+        errors.push(err);
+    }
+    if let Err(err) = read_channels(&doc, &mut config) {
+        errors.extend(err);
+    }
+    if let Err(err) = read_pri_sequence(&doc, &mut config) {
+        errors.extend(err);
+    }
+
+    if errors.is_empty() { Ok((config, warnings)) } else { Err(errors) }
+}
+
+pub fn from_bytes(bytes: &[u8]) -> Imported {
+    from_str(import::text(bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_with_units() {
+        let config = RadarConfig { config_name: "Export".to_string(), nb_agilities: 2, ..RadarConfig::default() };
+        let doc = to_value(&config);
+        assert_eq!(doc["schema_version"], json!(SCHEMA_VERSION));
+        assert_eq!(doc["config_name"], json!("Export"));
+        assert_eq!(doc["bistatic"], json!(false));
+        assert_eq!(doc["carrier"]["height"], json!({ "value": 3000.0, "unit": "m" }));
+        assert_eq!(doc["tx"]["pri"], json!({ "value": 100.0, "unit": "us" }));
+        assert_eq!(doc["tx"]["nb_agilities"], json!({ "value": 2, "unit": "count" }));
+        for field in FIELDS {
+            assert!(doc[field.section][field.key]["unit"] == json!(field.unit), "{}", field.path());
+        }
+        assert!(to_string(&config).ends_with("}\n"));
+    }
+
+    fn errors(text: &str) -> Vec<String> {
+        from_str(text).err().unwrap_or_default()
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut config = RadarConfig {
+            config_name: "Round trip".to_string(),
+            bsar_config: true,
+            earth_model: EarthModel::Wgs84,
+            nb_agilities: 2,
+            nb_channels: 2,
+            pri_mode: PriMode::List,
+            pri_list: vec![90.0, 110.0],
+            ..RadarConfig::default()
+        };
+        config.resize_agilities();
+        config.resize_channels();
+        config.agility_offsets[1] = 25.0;
+        config.channels[1].gain = -3.0;
+        let (imported, warnings) = from_str(&to_string(&config)).unwrap();
+        assert!(imported == config && warnings.is_empty());
+    }
+
+    #[test]
+    fn unversioned_documents() {
+        let (config, _) = from_str(r#"{ "config_name": "Old", "bsar_config": false, "carrier_height": 5000.0,
+            "look_angle": 30.0, "pri": 200.0, "nb_agilities": 2, "nb_channels": 3 }"#).unwrap();
+        assert_eq!(config.config_name, "Old");
+        assert_eq!((config.carrier_height, config.rx_carrier_height), (5000.0, 5000.0));
+        assert_eq!((config.look_angle, config.rx_look_angle), (30.0, 30.0));
+        assert_eq!((config.pri, config.pri_list.as_slice()), (200.0, [200.0].as_slice()));
+        assert_eq!(config.agility_offsets, [0.0, 0.0]);
+        assert_eq!(config.channels.len(), 3);
+        assert_eq!(config.pri_steps, RadarConfig::default().pri_steps);
+        assert_eq!(config.adc_noise_floor, RadarConfig::default().adc_noise_floor);
+
+        let errors = errors(r#"{ "config_name": "Old", "bsar_config": false, "look_angle": 95.0,
+            "nb_agilities": 1.5, "nb_channels": 1e10 }"#);
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].starts_with("carrier.look_angle: 95 deg is out of range"));
+        assert!(errors[1].starts_with("receiver.look_angle: 95 deg is out of range"));
+        assert_eq!(errors[2], "tx.nb_agilities: 1.5 is not an integer");
+        assert!(errors[3].starts_with("rx.nb_channels: 10000000000 count is out of range"));
+    }
+
+    #[test]
+    fn first_version_documents() {
+        // Only the parameters of the first version of the schema.
+        let mut doc = Map::new();
+        for (key, value) in to_value(&RadarConfig { carrier_height: 5000.0, pri: 200.0, nb_agilities: 2, ..RadarConfig::default() })
+            .as_object()
+            .unwrap()
+        {
+            match value {
+                Value::Object(entries) => {
+                    for (entry, quantity) in entries.iter().filter(|(entry, _)| in_first_version(key, entry)) {
+                        insert_quantity(&mut doc, key, entry, quantity["value"].clone(), quantity["unit"].as_str().unwrap());
+                    }
+                }
+                _ if FIRST_VERSION_ENTRIES.contains(&key.as_str()) => {
+                    doc.insert(key.clone(), value.clone());
+                }
+                _ => {}
+            }
+        }
+        let (config, warnings) = from_str(&Value::Object(doc).to_string()).unwrap();
+        assert_eq!(config.rx_carrier_height, 5000.0);
+        assert_eq!((config.pri, config.pri_list.as_slice()), (200.0, [200.0].as_slice()));
+        assert_eq!(config.agility_offsets, [0.0, 0.0]);
+        assert_eq!(config.earth_model, RadarConfig::default().earth_model);
+        assert!(warnings.contains(&"earth_model: not in the file, default value kept".to_string()));
+        assert!(warnings.contains(&"receiver.height: not in the file, carrier value kept".to_string()));
+        assert!(warnings.contains(&"tx.pri_list: not in the file, single PRI kept".to_string()));
+        assert!(warnings.contains(&"rx.channels: not in the file, default value kept".to_string()));
+    }
+
+    #[test]
+    fn validation() {
+        let doc = to_value(&RadarConfig::default());
+        let with = |path: [&str; 2], value: Value| {
+            let mut doc = doc.clone();
+            doc[path[0]][path[1]] = value;
+            errors(&doc.to_string())
+        };
+        assert_eq!(with(["tx", "pri"], json!({ "value": 0.5, "unit": "us" })), ["tx.pri: 0.5 us is out of range [1, inf]"]);
+        assert_eq!(with(["rx", "nb_channels"], json!({ "value": 2.5, "unit": "count" }))[0], "rx.nb_channels: 2.5 is not an integer");
+        assert_eq!(with(["tx", "duration"], json!({ "value": 10.0, "unit": "s" })), ["tx.duration: expected unit \"us\", found \"s\""]);
+        assert_eq!(with(["tx", "pri_mode"], json!("random")), ["tx.pri_mode: unknown mode \"random\""]);
+        assert_eq!(
+            with(["tx", "pri_list"], json!({ "value": [100.0, 0.5], "unit": "us" })),
+            ["tx.pri_list: expected a non-empty list of PRIs of at least 1 us"]
+        );
+        assert_eq!(with(["tx", "pri_steps"], json!({ "value": 1e6, "unit": "count" })), ["tx.pri_steps: 1000000 count is out of range [1, 256]"]);
+        assert_eq!(
+            with(["tx", "pri_list"], json!({ "value": vec![100.0; 300], "unit": "us" })),
+            ["tx.pri_list: 300 PRIs, more than the 256 of a sequence"]
+        );
+
+        let mut missing = doc.clone();
+        missing["carrier"].as_object_mut().unwrap().remove("height");
+        missing.as_object_mut().unwrap().remove("config_name");
+        assert_eq!(errors(&missing.to_string()), ["config_name: missing", "carrier.height: missing"]);
+
+        let newer = json!({ "schema_version": SCHEMA_VERSION + 1 });
+        assert!(errors(&newer.to_string())[0].contains("is newer than the supported version"));
+        assert_eq!(errors("[]"), ["the document is not a JSON object"]);
+    }
+}
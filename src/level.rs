@@ -0,0 +1,94 @@
+use std::f64::consts::PI;
+use crate::bistatic;
+use crate::config::{C, RadarConfig};
+use crate::sensitivity::{BOLTZMANN, T0, from_db, to_db};
+
+pub fn to_dbm(power_w: f64) -> f64 {
+    to_db(power_w * 1e3)
+}
+
+// Ground area contributing to the echo at one instant for a target seen under
+// `look_angle_deg`: the pulse length projected on the ground times the azimuth
+// footprint of the beam, in m².
+pub fn range_cell_area(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    let range = config.slant_range_at(look_angle_deg);
+    let pulse_length = C * config.tx_duration * 1e-6 / bistatic::ground_range_gradient(config, look_angle_deg);
+    pulse_length * range * config.azimuth_aperture_angle.to_radians()
+}
+
+// Echo power at the antenna port for the range cell seen under `look_angle_deg`, in W.
+pub fn received_power(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    let range = config.slant_range_at(look_angle_deg);
+    let rx_range = bistatic::receiver_range(config, look_angle_deg);
+    let sigma = from_db(config.retrodiff) * range_cell_area(config, look_angle_deg);
+    config.peak_power * from_db(2.0 * config.gain_antenna) * config.wavelength().powi(2) * sigma
+        / ((4.0 * PI).powi(3) * (range * rx_range).powi(2) * from_db(config.loss_power))
+}
+
+// Echo level at the ADC input, after the receiver gain, in dBm.
+pub fn adc_level(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    to_dbm(received_power(config, look_angle_deg)) + config.rx_gain
+}
+
+// Thermal noise level at the ADC input over the signal bandwidth, in dBm.
+pub fn thermal_noise_level(config: &RadarConfig) -> f64 {
+    to_dbm(BOLTZMANN * T0 * config.bandwidth * 1e6) + config.noise_factor + config.rx_gain
+}
+
+// Echo level at the ADC input across the swath, as `[ground range (m), level (dBm)]` points.
+pub fn adc_level_profile(config: &RadarConfig, nb_points: usize) -> Vec<[f64; 2]> {
+    config.swath_angles(nb_points)
+        .into_iter()
+        .map(|angle| [config.ground_range(angle), adc_level(config, angle)])
+        .filter(|[_, level]| level.is_finite())
+        .collect()
+}
+
+pub fn warnings(config: &RadarConfig, profile: &[[f64; 2]]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if let Some(max) = profile.iter().max_by(|a, b| a[1].total_cmp(&b[1]))
+        && max[1] > config.adc_full_scale
+    {
+        warnings.push(format!(
+            "the echo at {:.1} m reaches {:.1} dBm and saturates the ADC (full scale {:.1} dBm)",
+            max[0], max[1], config.adc_full_scale
+        ));
+    }
+    if let Some(min) = profile.iter().min_by(|a, b| a[1].total_cmp(&b[1]))
+        && min[1] < config.adc_noise_floor
+    {
+        warnings.push(format!(
+            "the echo at {:.1} m drops to {:.1} dBm, below the ADC noise floor ({:.1} dBm)",
+            min[0], min[1], config.adc_noise_floor
+        ));
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_budget() {
+        let config = RadarConfig {
+            peak_power: 100.0,
+            gain_antenna: 20.0,
+            center_frequency: 9.6,
+            bandwidth: 100.0,
+            azimuth_aperture_angle: 10.0,
+            retrodiff: -10.0,
+            rx_gain: 30.0,
+            ..RadarConfig::default()
+        };
+        // 30 dB more receiver gain raises the ADC level by 30 dB.
+        let boosted = RadarConfig { rx_gain: 60.0, ..config.clone() };
+        assert!((adc_level(&boosted, 45.0) - adc_level(&config, 45.0) - 30.0).abs() < 1e-9);
+        assert!((thermal_noise_level(&config) - (-174.0 + 80.0 + 30.0)).abs() < 0.1);
+
+        let profile = adc_level_profile(&boosted, 11);
+        let saturating = RadarConfig { adc_full_scale: profile[0][1] - 1.0, ..boosted.clone() };
+        assert_eq!(warnings(&saturating, &profile).len(), 1);
+        assert!(warnings(&boosted, &profile).is_empty());
+    }
+}
@@ -0,0 +1,25 @@
+pub mod ambiguity;
+pub mod antenna;
+pub mod bistatic;
+pub mod chronogram;
+pub mod config;
+pub mod diamond;
+pub mod earth;
+pub mod figure;
+pub mod format;
+pub mod geometry;
+pub mod import;
+pub mod interferometry;
+pub mod json;
+pub mod level;
+pub mod sameva;
+pub mod genio;
+pub mod vst;
+pub mod pamela;
+pub mod pri;
+pub mod resolution;
+pub mod sensitivity;
+pub mod timing;
+
+pub use config::RadarConfig;
+pub use format::Format;
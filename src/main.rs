@@ -1,143 +1,203 @@
 use eframe::egui;
-mod geometry;
-mod chronogram;
-
-const C: f64 = 299792458.0; // Speed of light in m/s
+use sarconf::earth::EarthModel;
+use sarconf::figure::{Bounds, Figure, ImageFormat};
+use sarconf::{Format, RadarConfig, ambiguity, chronogram, config, geometry, interferometry, level, pri, resolution, sensitivity, timing};
+mod history;
+mod plot;
+mod profile;
+mod diagram;
 
+#[derive(Default)]
 struct SARConfApp {
-    config_name: String,
-    bsar_config: bool,
-
-    // Antenna parameters
-    elevation_aperture_angle: f64,
-    azimuth_aperture_angle: f64,
-
-    // Geometry parameters
-    carrier_velocity: f64,
-    carrier_height: f64,
-    look_angle: f64,
-
-    // Transmission parameters
-    pri: f64,
-    tx_offset: f64,
-    tx_duration: f64,
-    nb_agilities: u32,
-
-    // Receiver parameters
-    nb_channels: u32,
-    fech: f64,
-    rx_offset: f64,
-    rx_duration: f64,
-    rx_noise_offset: f64,
-    rx_noise_duration: f64,
-    rx_reinj_offset: f64,
-    rx_reinj_duration: f64,
-
-    // SAR sensitivity parameters
-    peak_power: f64,
-    loss_power: f64,
-    gain_antenna: f64,
-    noise_factor: f64,
-    center_frequency: f64,
-    bandwidth: f64,
-
-    // Level parameters
-    retrodiff: f64,
-    rx_gain: f64,
+    config: RadarConfig,
+    history: history::History,
+    report: Option<Report>,
+}
 
-    // Interference parameters
-    height_ambiguity: f64,
-    accuracy_height_ambiguity: f64,
+struct Report {
+    title: String,
+    lines: Vec<String>,
 }
 
-impl Default for SARConfApp {
-    fn default() -> Self {
-        Self {
-            config_name: String::from("Untitled"),
-            bsar_config: false,
-            elevation_aperture_angle: 18.0,
-            azimuth_aperture_angle: 0.0,
-            carrier_velocity: 120.0,
-            carrier_height: 3000.0,
-            look_angle: 45.0,
-            pri: 100.0,
-            tx_offset: 0.0,
-            tx_duration: 10.0,
-            nb_agilities: 1,
-            nb_channels: 1,
-            fech: 0.0,
-            rx_offset: 24.0,
-            rx_duration: 21.0,
-            rx_noise_offset: 15.0,
-            rx_noise_duration: 3.0,
-            rx_reinj_offset: 20.0,
-            rx_reinj_duration: 3.0,
-            peak_power: 0.0,
-            loss_power: 0.0,
-            gain_antenna: 0.0,
-            noise_factor: 0.0,
-            center_frequency: 0.0,
-            bandwidth: 0.0,
-            retrodiff: 0.0,
-            rx_gain: 0.0,
-            height_ambiguity: 0.0,
-            accuracy_height_ambiguity: 0.0,
+impl SARConfApp {
+    fn save_as(&mut self, format: Format) {
+        let contents = match format.export(&self.config) {
+            Ok(contents) => contents,
+            Err(err) => {
+                self.report = Some(Report {
+                    title: "Export failed".to_string(),
+                    lines: vec![err],
+                });
+                return;
+            }
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(format.name(), &[format.extension()])
+            .set_file_name(format!("{}.{}", self.config.config_name, format.extension()))
+            .save_file()
+        else {
+            return;
+        };
+        if let Err(err) = std::fs::write(&path, contents) {
+            self.report = Some(Report {
+                title: "Export failed".to_string(),
+                lines: vec![format!("{}: {}", path.display(), err)],
+            });
+        }
+    }
+
+    fn save_figure(&mut self, figure: &Figure, bounds: Bounds) {
+        let mut dialog = rfd::FileDialog::new();
+        for format in ImageFormat::ALL {
+            dialog = dialog.add_filter(format.name(), &[format.extension()]);
+        }
+        let Some(path) = dialog.set_file_name(format!("{}.png", figure.title)).save_file() else {
+            return;
+        };
+        let result = ImageFormat::from_path(&path)
+            .ok_or_else(|| "unknown image format, expected .png or .svg".to_string())
+            .and_then(|format| format.render(figure, bounds))
+            .and_then(|bytes| std::fs::write(&path, bytes).map_err(|err| err.to_string()));
+        if let Err(err) = result {
+            self.report = Some(Report {
+                title: "Export failed".to_string(),
+                lines: vec![format!("{}: {}", path.display(), err)],
+            });
         }
     }
-}
 
-fn input(ui: &mut egui::Ui, label: &str, tooltip: Option<&str>, widget: impl egui::Widget) {
-    ui.horizontal(|ui| {
-        ui.label(label);
-        if let Some(text) = tooltip {
-            let hover_text = egui::RichText::new(text)
-                .color(egui::Color32::from_rgb(200, 200, 200))
-                .monospace();
-            ui.add(egui::Label::new("❓").sense(egui::Sense::click())).on_hover_text(hover_text);
+    fn open(&mut self, format: Format) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(format.name(), &[format.extension()])
+            .pick_file()
+        else {
+            return;
+        };
+        let result = std::fs::read(&path)
+            .map_err(|err| vec![err.to_string()])
+            .and_then(|bytes| format.import(&bytes));
+        match result {
+            Ok((config, warnings)) => {
+                self.config = config;
+                self.history.push(format!("Import {}", path.display()), &self.config);
+                self.report = None;
+                if !warnings.is_empty() {
+                    self.report = Some(Report {
+                        title: format!("Imported {}", path.display()),
+                        lines: warnings,
+                    });
+                }
+            }
+            Err(lines) => {
+                self.report = Some(Report {
+                    title: format!("Import of {} failed", path.display()),
+                    lines,
+                });
+            }
         }
-        ui.add(widget);
-    });
+    }
 }
 
 impl eframe::App for SARConfApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.config.resize_agilities();
+        self.config.resize_channels();
+
+        // Undo and redo, unless a text field handles its own undo.
+        if !ctx.wants_keyboard_input() {
+            let redo = ctx.input_mut(|input| {
+                input.consume_shortcut(&egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z))
+                    || input.consume_shortcut(&egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Y))
+            });
+            let undo = ctx.input_mut(|input| {
+                input.consume_shortcut(&egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z))
+            });
+            let config = if redo { self.history.redo() } else if undo { self.history.undo() } else { None };
+            if let Some(config) = config {
+                self.config = config.clone();
+            }
+        }
+
         egui::TopBottomPanel::top("top_panel")
             .show(ctx, |ui| {
                 ui.horizontal(|ui| {
                     egui::global_theme_preference_switch(ui);
                     ui.separator();
                     ui.label("Configuration Name:");
-                    ui.text_edit_singleline(&mut self.config_name);
+                    ui.text_edit_singleline(&mut self.config.config_name);
                     if ui.button("Reset").clicked() {
-                        *self = SARConfApp::default();
+                        self.config = RadarConfig::default();
+                        self.history.push("Reset".to_string(), &self.config);
+                        self.report = None;
                     }
+                    ui.separator();
+                    if ui.add_enabled(self.history.can_undo(), egui::Button::new("⟲"))
+                        .on_hover_text("Undo (Ctrl+Z)")
+                        .clicked()
+                        && let Some(config) = self.history.undo()
+                    {
+                        self.config = config.clone();
+                    }
+                    if ui.add_enabled(self.history.can_redo(), egui::Button::new("⟳"))
+                        .on_hover_text("Redo (Ctrl+Shift+Z)")
+                        .clicked()
+                        && let Some(config) = self.history.redo()
+                    {
+                        self.config = config.clone();
+                    }
+                    let history_button = ui.button("History");
+                    egui::Popup::menu(&history_button)
+                        .show(|ui| {
+                            egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                                let mut selected = None;
+                                for (i, entry) in self.history.entries().iter().enumerate().rev() {
+                                    if ui.selectable_label(i == self.history.current(), &entry.label).clicked() {
+                                        selected = Some(i);
+                                    }
+                                }
+                                if let Some(i) = selected {
+                                    self.config = self.history.jump(i).clone();
+                                }
+                            });
+                        });
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         let export_button = ui.button("Export");
                         egui::Popup::menu(&export_button)
                             .show(|ui| {
-                                if ui.button("Export as JSON (.json)").clicked() {
-                                    // Export as JSON logic here
-                                }
-                                if ui.button("Export as SAMEVA (.init)").clicked() {
-                                    // Export as SAMEVA logic here
-                                }
-                                if ui.button("Export as GENIO (.io)").clicked() {
-                                    // Export as GENIO logic here
+                                for format in Format::ALL {
+                                    if ui.button(format!("Export as {} (.{})", format.name(), format.extension())).clicked() {
+                                        self.save_as(format);
+                                    }
                                 }
-                                if ui.button("Export as VST (.vst)").clicked() {
-                                    // Export as VST logic here
-                                }
-                                if ui.button("Export as PAMELA (.wave)").clicked() {
-                                    // Export as PAMELA logic here
+                            });
+                        let import_button = ui.button("Import");
+                        egui::Popup::menu(&import_button)
+                            .show(|ui| {
+                                for format in Format::ALL {
+                                    if ui.button(format!("Import {} (.{})", format.name(), format.extension())).clicked() {
+                                        self.open(format);
+                                    }
                                 }
                             });
-                        if ui.button("Import").clicked() {
-                            // Import logic here
-                        }
                     });
                 });
             });
 
+        if let Some(report) = &self.report {
+            let mut open = true;
+            egui::Window::new(&report.title)
+                .open(&mut open)
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    for line in &report.lines {
+                        ui.label(line);
+                    }
+                });
+            if !open {
+                self.report = None;
+            }
+        }
+
         egui::SidePanel::left("left_panel")
             .show(ctx, |ui| {
                 ui.heading("Transmitter");
@@ -151,18 +211,18 @@ impl eframe::App for SARConfApp {
                             .show(ui, |ui| {
                                 ui.label("Height:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.carrier_height)
+                                    egui::DragValue::new(&mut self.config.carrier_height)
                                         .fixed_decimals(3)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" m")
                                 );
                                 ui.end_row();
                                 ui.label("");
-                                ui.label(format!("{:.3} ft", self.carrier_height/0.3048));
+                                ui.label(format!("{:.3} ft", self.config.carrier_height/0.3048));
                                 ui.end_row();
                                 ui.label("Velocity:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.carrier_velocity)
+                                    egui::DragValue::new(&mut self.config.carrier_velocity)
                                         .fixed_decimals(3)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" m/s")
@@ -170,13 +230,31 @@ impl eframe::App for SARConfApp {
                                 ui.end_row();
                                 ui.label("Look Angle:");
                                 ui.add(
-                                    egui::Slider::new(&mut self.look_angle, 0.0..=90.0)
+                                    egui::Slider::new(&mut self.config.look_angle, 0.0..=90.0)
                                         .fixed_decimals(3)
                                         .trailing_fill(true)
                                         .drag_value_speed(1.0)
                                         .suffix("°")
                                 );
                                 ui.end_row();
+                                ui.label("Earth:");
+                                egui::ComboBox::from_id_salt("earth_model")
+                                    .selected_text(self.config.earth_model.name())
+                                    .show_ui(ui, |ui| {
+                                        for model in EarthModel::ALL {
+                                            ui.selectable_value(&mut self.config.earth_model, model, model.name());
+                                        }
+                                    });
+                                ui.end_row();
+                                ui.label("Latitude:");
+                                ui.add_enabled(
+                                    self.config.earth_model == EarthModel::Wgs84,
+                                    egui::DragValue::new(&mut self.config.latitude)
+                                        .fixed_decimals(3)
+                                        .range(-90.0..=90.0)
+                                        .suffix("°")
+                                );
+                                ui.end_row();
                             });
                     });
                 egui::CollapsingHeader::new("Antenna")
@@ -189,7 +267,7 @@ impl eframe::App for SARConfApp {
                             .show(ui, |ui| {
                                 ui.label("Elevation:");
                                 ui.add(
-                                    egui::Slider::new(&mut self.elevation_aperture_angle, 0.0..=360.0)
+                                    egui::Slider::new(&mut self.config.elevation_aperture_angle, 0.0..=360.0)
                                         .fixed_decimals(3)
                                         .trailing_fill(true)
                                         .drag_value_speed(1.0)
@@ -198,7 +276,7 @@ impl eframe::App for SARConfApp {
                                 ui.end_row();
                                 ui.label("Azimuth:");
                                 ui.add(
-                                    egui::Slider::new(&mut self.azimuth_aperture_angle, 0.0..=360.0)
+                                    egui::Slider::new(&mut self.config.azimuth_aperture_angle, 0.0..=360.0)
                                         .fixed_decimals(3)
                                         .trailing_fill(true)
                                         .drag_value_speed(1.0)
@@ -207,7 +285,7 @@ impl eframe::App for SARConfApp {
                                 ui.end_row();
                                 ui.label("Gain (one-way):");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.gain_antenna)
+                                    egui::DragValue::new(&mut self.config.gain_antenna)
                                         .fixed_decimals(1)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" dB")
@@ -225,7 +303,7 @@ impl eframe::App for SARConfApp {
                             .show(ui, |ui| {
                                 ui.label("Center Freq.:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.center_frequency)
+                                    egui::DragValue::new(&mut self.config.center_frequency)
                                         .fixed_decimals(3)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" GHz")
@@ -233,32 +311,35 @@ impl eframe::App for SARConfApp {
                                 ui.end_row();
                                 ui.label("Bandwidth:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.bandwidth)
+                                    egui::DragValue::new(&mut self.config.bandwidth)
                                         .fixed_decimals(1)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" MHz")
                                 );
                                 ui.end_row();
                                 ui.label("Nb of Agilities:");
-                                ui.add(
-                                    egui::DragValue::new(&mut self.nb_agilities)
-                                        .range(1..=u32::MAX)
-                                );
+                                // Resized at once, for the panels below and the history to see a consistent configuration.
+                                if ui.add(
+                                    egui::DragValue::new(&mut self.config.nb_agilities)
+                                        .range(1..=config::MAX_AGILITIES)
+                                ).changed() {
+                                    self.config.resize_agilities();
+                                }
                                 ui.end_row();
                                 ui.label("PRI:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.pri)
+                                    egui::DragValue::new(&mut self.config.pri)
                                         .fixed_decimals(1)
-                                        .range(1.0..=f64::NAN)
+                                        .range(config::MIN_PRI..=f64::NAN)
                                         .suffix(" µs")
                                 );
                                 ui.end_row();
                                 ui.label("PRF:");
-                                ui.label(format!("{:.1} Hz", 1e6/self.pri));
+                                ui.label(format!("{:.1} Hz", self.config.prf()));
                                 ui.end_row();
                                 ui.label("Pulse Offset:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.tx_offset)
+                                    egui::DragValue::new(&mut self.config.tx_offset)
                                         .fixed_decimals(1)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" µs")
@@ -266,7 +347,7 @@ impl eframe::App for SARConfApp {
                                 ui.end_row();
                                 ui.label("Pulse Duration:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.tx_duration)
+                                    egui::DragValue::new(&mut self.config.tx_duration)
                                         .fixed_decimals(1)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" µs")
@@ -274,7 +355,7 @@ impl eframe::App for SARConfApp {
                                 ui.end_row();
                                 ui.label("Peak Power:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.peak_power)
+                                    egui::DragValue::new(&mut self.config.peak_power)
                                         .fixed_decimals(1)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" W")
@@ -282,7 +363,7 @@ impl eframe::App for SARConfApp {
                                 ui.end_row();
                                 ui.label("Loss Power:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.loss_power)
+                                    egui::DragValue::new(&mut self.config.loss_power)
                                         .fixed_decimals(1)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" dB")
@@ -290,16 +371,133 @@ impl eframe::App for SARConfApp {
                                 ui.end_row();
                             });
                     });
+                egui::CollapsingHeader::new("Agility")
+                    .default_open(self.config.nb_agilities > 1)
+                    .show(ui, |ui| {
+                        egui::Grid::new("tx_agility_grid")
+                            .num_columns(2)
+                            .striped(false)
+                            .spacing([20.0, 5.0])
+                            .show(ui, |ui| {
+                                for step in 0..self.config.agility_offsets.len() as u32 {
+                                    ui.label(format!("F{} Offset:", step));
+                                    ui.add(
+                                        egui::DragValue::new(&mut self.config.agility_offsets[step as usize])
+                                            .fixed_decimals(1)
+                                            .suffix(" MHz")
+                                    );
+                                    ui.end_row();
+                                    ui.label("");
+                                    ui.label(format!("{:.4} GHz", self.config.agility_frequency(step)));
+                                    ui.end_row();
+                                }
+                            });
+                        ui.label("Sequence:");
+                        let mut sequence = self.config.agility_sequence();
+                        ui.horizontal_wrapped(|ui| {
+                            for position in 0..sequence.len() {
+                                if position > 0 && ui.small_button("⇄").on_hover_text("Swap").clicked() {
+                                    sequence.swap(position - 1, position);
+                                }
+                                ui.label(format!("F{}", sequence[position]));
+                            }
+                        });
+                        if sequence != self.config.agility_sequence() {
+                            self.config.agility_sequence = sequence;
+                        }
+                    });
+                egui::CollapsingHeader::new("PRI Sequence")
+                    .default_open(self.config.pri_mode != pri::PriMode::Constant)
+                    .show(ui, |ui| {
+                        egui::Grid::new("tx_pri_grid")
+                            .num_columns(2)
+                            .striped(false)
+                            .spacing([20.0, 5.0])
+                            .show(ui, |ui| {
+                                ui.label("Mode:");
+                                egui::ComboBox::from_id_salt("pri_mode")
+                                    .selected_text(self.config.pri_mode.name())
+                                    .show_ui(ui, |ui| {
+                                        for mode in pri::PriMode::ALL {
+                                            ui.selectable_value(&mut self.config.pri_mode, mode, mode.name());
+                                        }
+                                    });
+                                ui.end_row();
+                                match self.config.pri_mode {
+                                    pri::PriMode::Constant => {}
+                                    pri::PriMode::Staggered | pri::PriMode::Jittered => {
+                                        ui.label("Nb of PRIs:");
+                                        ui.add(
+                                            egui::DragValue::new(&mut self.config.pri_steps)
+                                                .range(1..=config::MAX_PRI_STEPS)
+                                        );
+                                        ui.end_row();
+                                        let (label, value) = if self.config.pri_mode == pri::PriMode::Staggered {
+                                            ("Stagger:", &mut self.config.pri_stagger)
+                                        } else {
+                                            ("Jitter:", &mut self.config.pri_jitter)
+                                        };
+                                        ui.label(label);
+                                        ui.add(
+                                            egui::DragValue::new(value)
+                                                .fixed_decimals(1)
+                                                .range(0.0..=f64::NAN)
+                                                .suffix(" µs")
+                                        );
+                                        ui.end_row();
+                                    }
+                                    pri::PriMode::List => {
+                                        let mut removed = None;
+                                        let removable = self.config.pri_list.len() > 1;
+                                        for (i, pri) in self.config.pri_list.iter_mut().enumerate() {
+                                            ui.label(format!("PRI {}:", i));
+                                            ui.horizontal(|ui| {
+                                                ui.add(
+                                                    egui::DragValue::new(pri)
+                                                        .fixed_decimals(1)
+                                                        .range(config::MIN_PRI..=f64::NAN)
+                                                        .suffix(" µs")
+                                                );
+                                                if removable && ui.small_button("➖").on_hover_text("Remove").clicked() {
+                                                    removed = Some(i);
+                                                }
+                                            });
+                                            ui.end_row();
+                                        }
+                                        if let Some(i) = removed {
+                                            self.config.pri_list.remove(i);
+                                        }
+                                        if self.config.pri_list.len() < config::MAX_PRI_STEPS as usize {
+                                            ui.label("");
+                                            if ui.small_button("➕").on_hover_text("Add a PRI").clicked() {
+                                                self.config.pri_list.push(self.config.pri);
+                                            }
+                                            ui.end_row();
+                                        }
+                                    }
+                                }
+                                let sequence = self.config.raw_pri_sequence();
+                                if sequence.len() > 1 {
+                                    let shown: Vec<String> = sequence.iter().take(8).map(|pri| format!("{:.1}", pri)).collect();
+                                    ui.label("Sequence:");
+                                    ui.label(format!("{}{} µs", shown.join(", "), if sequence.len() > 8 { ", …" } else { "" }));
+                                    ui.end_row();
+                                }
+                                ui.label("Mean PRF:");
+                                ui.label(format!("{:.1} Hz", self.config.mean_prf()));
+                                ui.end_row();
+                            });
+                    });
             });
 
         egui::SidePanel::right("right_panel")
             .show(ctx, |ui| {
                 ui.heading("Receiver");
-                ui.add(egui::Checkbox::new(&mut self.bsar_config, "bistatic configuration"));
+                ui.add(egui::Checkbox::new(&mut self.config.bsar_config, "bistatic configuration"));
                 egui::CollapsingHeader::new("Carrier")
                     .default_open(true)
                     .show(ui, |ui| {
-                        ui.add_enabled_ui(self.bsar_config, |ui| {
+                        ui.add_enabled_ui(self.config.bsar_config, |ui| {
                             egui::Grid::new("rx_carrier_grid")
                                 .num_columns(2)
                                 .striped(false)
@@ -307,18 +505,18 @@ impl eframe::App for SARConfApp {
                                 .show(ui, |ui| {
                                     ui.label("Height:");
                                     ui.add(
-                                        egui::DragValue::new(&mut self.carrier_height)
+                                        egui::DragValue::new(&mut self.config.rx_carrier_height)
                                             .fixed_decimals(3)
                                             .range(0.0..=f64::NAN)
                                             .suffix(" m")
                                     );
                                     ui.end_row();
                                     ui.label("");
-                                    ui.label(format!("{:.3} ft", self.carrier_height/0.3048));
+                                    ui.label(format!("{:.3} ft", self.config.rx_carrier_height/0.3048));
                                     ui.end_row();
                                     ui.label("Velocity:");
                                     ui.add(
-                                        egui::DragValue::new(&mut self.carrier_velocity)
+                                        egui::DragValue::new(&mut self.config.rx_carrier_velocity)
                                             .fixed_decimals(3)
                                             .range(0.0..=f64::NAN)
                                             .suffix(" m/s")
@@ -326,13 +524,27 @@ impl eframe::App for SARConfApp {
                                     ui.end_row();
                                     ui.label("Look Angle:");
                                     ui.add(
-                                        egui::Slider::new(&mut self.look_angle, 0.0..=90.0)
+                                        egui::Slider::new(&mut self.config.rx_look_angle, 0.0..=90.0)
                                             .fixed_decimals(3)
                                             .trailing_fill(true)
                                             .drag_value_speed(1.0)
                                             .suffix("°")
                                     );
                                     ui.end_row();
+                                    ui.label("Along-track baseline:");
+                                    ui.add(
+                                        egui::DragValue::new(&mut self.config.rx_along_track_baseline)
+                                            .fixed_decimals(3)
+                                            .suffix(" m")
+                                    );
+                                    ui.end_row();
+                                    ui.label("Across-track baseline:");
+                                    ui.add(
+                                        egui::DragValue::new(&mut self.config.rx_across_track_baseline)
+                                            .fixed_decimals(3)
+                                            .suffix(" m")
+                                    );
+                                    ui.end_row();
                                 });
                         });
                     });
@@ -346,14 +558,14 @@ impl eframe::App for SARConfApp {
                             .show(ui, |ui| {
                                 ui.label("Fech I/O:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.fech)
+                                    egui::DragValue::new(&mut self.config.fech)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" MHz")
                                 );
                                 ui.end_row();
                                 ui.label("RX Offset:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.rx_offset)
+                                    egui::DragValue::new(&mut self.config.rx_offset)
                                         .fixed_decimals(1)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" µs")
@@ -361,48 +573,70 @@ impl eframe::App for SARConfApp {
                                 ui.end_row();
                                 ui.label("RX Duration:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.rx_duration)
+                                    egui::DragValue::new(&mut self.config.rx_duration)
+                                        .range(0.0..=f64::NAN)
+                                        .suffix(" µs")
+                                );
+                                ui.end_row();
+                                ui.label("RX Margin:");
+                                ui.add(
+                                    egui::DragValue::new(&mut self.config.rx_margin)
+                                        .fixed_decimals(1)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" µs")
                                 );
                                 ui.end_row();
+                                ui.label("");
+                                let fitted = self.config.fitted_rx_window();
+                                if ui.add_enabled(fitted.is_some(), egui::Button::new("Fit RX window to beam"))
+                                    .on_hover_text("Set the RX window to receive the whole swath echo, plus the margin on each side")
+                                    .on_disabled_hover_text("The swath echo does not come back within a finite time")
+                                    .clicked()
+                                    && let Some(window) = fitted
+                                {
+                                    (self.config.rx_offset, self.config.rx_duration) = window;
+                                }
+                                ui.end_row();
                                 ui.label("RX Noise Offset:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.rx_noise_offset)
+                                    egui::DragValue::new(&mut self.config.rx_noise_offset)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" µs")
                                 );
                                 ui.end_row();
                                 ui.label("RX Noise Duration:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.rx_noise_duration)
+                                    egui::DragValue::new(&mut self.config.rx_noise_duration)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" µs")
                                 );
                                 ui.end_row();
                                 ui.label("RX Reinj Offset:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.rx_reinj_offset)
+                                    egui::DragValue::new(&mut self.config.rx_reinj_offset)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" µs")
                                 );
                                 ui.end_row();
                                 ui.label("RX Reinj Duration:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.rx_reinj_duration)
+                                    egui::DragValue::new(&mut self.config.rx_reinj_duration)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" µs")
                                 );
                                 ui.end_row();
                                 ui.label("Nb of Channels:");
-                                ui.add(
-                                    egui::DragValue::new(&mut self.nb_channels)
-                                        .range(1..=u32::MAX)
-                                );
+                                // Resized at once, like the agility offsets.
+                                if ui.add(
+                                    egui::DragValue::new(&mut self.config.nb_channels)
+                                        .range(1..=config::MAX_CHANNELS)
+                                ).changed() {
+                                    self.config.resize_channels();
+                                }
                                 ui.end_row();
                                 ui.label("Noise Factor:");
                                 ui.add(
-                                    egui::DragValue::new(&mut self.noise_factor)
+                                    egui::DragValue::new(&mut self.config.noise_factor)
                                         .fixed_decimals(1)
                                         .range(0.0..=f64::NAN)
                                         .suffix(" dB")
@@ -410,119 +644,542 @@ impl eframe::App for SARConfApp {
                                 ui.end_row();
                             });
                     });
+                egui::CollapsingHeader::new("Level")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        egui::Grid::new("rx_level_grid")
+                            .num_columns(2)
+                            .striped(false)
+                            .spacing([20.0, 5.0])
+                            .show(ui, |ui| {
+                                ui.label("Backscatter (σ0):");
+                                ui.add(
+                                    egui::DragValue::new(&mut self.config.retrodiff)
+                                        .fixed_decimals(1)
+                                        .suffix(" dB")
+                                );
+                                ui.end_row();
+                                ui.label("RX Gain:");
+                                ui.add(
+                                    egui::DragValue::new(&mut self.config.rx_gain)
+                                        .fixed_decimals(1)
+                                        .suffix(" dB")
+                                );
+                                ui.end_row();
+                                ui.label("ADC Full Scale:");
+                                ui.add(
+                                    egui::DragValue::new(&mut self.config.adc_full_scale)
+                                        .fixed_decimals(1)
+                                        .suffix(" dBm")
+                                );
+                                ui.end_row();
+                                ui.label("ADC Noise Floor:");
+                                ui.add(
+                                    egui::DragValue::new(&mut self.config.adc_noise_floor)
+                                        .fixed_decimals(1)
+                                        .suffix(" dBm")
+                                );
+                                ui.end_row();
+                            });
+                    });
+                egui::CollapsingHeader::new("Channels")
+                    .default_open(self.config.nb_channels > 1)
+                    .show(ui, |ui| {
+                        for i in 0..self.config.channels.len() {
+                            let delay = self.config.channel_delay(i);
+                            let channel = &mut self.config.channels[i];
+                            ui.checkbox(&mut channel.enabled, format!("Channel {}", i));
+                            ui.add_enabled_ui(channel.enabled, |ui| {
+                                egui::Grid::new(("rx_channel_grid", i))
+                                    .num_columns(2)
+                                    .striped(false)
+                                    .spacing([20.0, 5.0])
+                                    .show(ui, |ui| {
+                                        for (label, value) in ["Along Track:", "Across Track:", "Up:"].into_iter().zip(&mut channel.position) {
+                                            ui.label(label);
+                                            ui.add(
+                                                egui::DragValue::new(value)
+                                                    .fixed_decimals(3)
+                                                    .speed(0.01)
+                                                    .suffix(" m")
+                                            );
+                                            ui.end_row();
+                                        }
+                                        ui.label("Cable Delay:");
+                                        ui.add(
+                                            egui::DragValue::new(&mut channel.cable_delay)
+                                                .fixed_decimals(1)
+                                                .suffix(" ns")
+                                        );
+                                        ui.end_row();
+                                        ui.label("Gain:");
+                                        ui.add(
+                                            egui::DragValue::new(&mut channel.gain)
+                                                .fixed_decimals(1)
+                                                .suffix(" dB")
+                                        );
+                                        ui.end_row();
+                                        ui.label("Echo Delay:");
+                                        ui.label(format!("{:.3} µs", delay));
+                                        ui.end_row();
+                                    });
+                            });
+                        }
+                    });
             });
 
         egui::TopBottomPanel::bottom("bottom_panel")
             .show(ctx, |ui| {
-                ui.horizontal(|ui| {
+                let save = ui.horizontal(|ui| {
                     ui.heading("Chronogram");
-                    if ui.button("📷").clicked() {
-                        // TODO: Save the current chronogram as a PNG file
-                    }
-                });
-                let mut windows = vec![
-                    chronogram::Window {
-                        name: "TX".to_string(),
-                        start_time: self.tx_offset,
-                        duration: self.tx_duration,
-                        height: 1.0,
-                        dashed: false,
-                        color: Some(egui::Color32::RED),
-                    },
-                    chronogram::Window {
-                        name: "Nadir".to_string(),
-                        start_time: self.tx_offset + self.carrier_height / C * 2e6,
-                        duration: self.tx_duration,
-                        height: 0.2,
-                        dashed: true,
-                        color: Some(egui::Color32::WHITE),
-                    },
-                    chronogram::Window {
-                        name: "RX".to_string(),
-                        start_time: self.rx_offset,
-                        duration: self.rx_duration,
-                        height: 1.0,
-                        dashed: false,
-                        color: Some(egui::Color32::LIGHT_YELLOW),
-                    },
-                    chronogram::Window {
-                        name: "Noise".to_string(),
-                        start_time: self.rx_noise_offset,
-                        duration: self.rx_noise_duration,
-                        height: 0.8,
-                        dashed: false,
-                        color: Some(egui::Color32::GOLD),
-                    },
-                    chronogram::Window {
-                        name: "Reinj".to_string(),
-                        start_time: self.rx_reinj_offset,
-                        duration: self.rx_reinj_duration,
-                        height: 0.8,
-                        dashed: false,
-                        color: Some(egui::Color32::GOLD),
-                    },
-                ];
-                if self.rx_duration - self.tx_offset - self.tx_duration > 0.0 {
-                    windows.push(
-                        chronogram::Window {
-                            name: "RX (full resol)".to_string(),
-                            start_time: self.rx_offset,
-                            duration: self.rx_duration - self.tx_offset - self.tx_duration,
-                            height: 1.0,
-                            dashed: true,
-                            color: Some(egui::Color32::YELLOW),
-                        },
-                    );
+                    ui.button("📷").on_hover_text("Save as PNG or SVG").clicked()
+                }).inner;
+                let figure = chronogram::figure(&self.config);
+                let bounds = plot::chronogram(ui, &figure);
+                if save {
+                    self.save_figure(&figure, bounds);
                 }
-                chronogram::plot(ui, self.pri, windows);
+                let violations = timing::violations(&self.config);
 
                 egui::Grid::new("rx_antenna_grid")
                     .num_columns(2)
                     .striped(true)
                     .spacing([20.0, 5.0])
                     .show(ui, |ui| {
+                        if self.config.pri_mode != pri::PriMode::Constant {
+                            ui.label("Mean PRF:");
+                            ui.label(format!("{:.1} Hz", self.config.mean_prf()));
+                            ui.end_row();
+                        }
                         ui.label("Final PRF:");
-                        ui.label(format!("{:.1} Hz", 1e6/self.pri/self.nb_agilities as f64));
+                        ui.label(format!("{:.1} Hz", self.config.final_prf()));
                         ui.end_row();                        
                     });
 
+                egui::CollapsingHeader::new(format!("Diagnostics ({})", violations.len()))
+                    .id_salt("diagnostics")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        if violations.is_empty() {
+                            ui.label("All timing constraints are met.");
+                        }
+                        for violation in &violations {
+                            ui.horizontal(|ui| {
+                                let color = match violation.severity {
+                                    timing::Severity::Error => egui::Color32::RED,
+                                    timing::Severity::Warning => egui::Color32::ORANGE,
+                                    timing::Severity::Info => egui::Color32::LIGHT_BLUE,
+                                };
+                                ui.colored_label(color, violation.severity.name());
+                                ui.label(&violation.message);
+                            });
+                        }
+                    });
+
             });
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.horizontal(|ui| {
-                ui.heading("Geometry");
-                if ui.button("📷").clicked() {
-                    // TODO: Save the current geometry as a PNG file
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                let save = ui.horizontal(|ui| {
+                    ui.heading("Geometry");
+                    ui.button("📷").on_hover_text("Save as PNG or SVG").clicked()
+                }).inner;
+                let figure = geometry::figure(&self.config);
+                let bounds = plot::geometry(ui, &figure);
+                if save {
+                    self.save_figure(&figure, bounds);
                 }
-            });
-            let min_aperture_elevation_angle_deg = self.look_angle - self.elevation_aperture_angle / 2.0;
-            let max_aperture_elevation_angle_deg = self.look_angle + self.elevation_aperture_angle / 2.0;
-            let min_numerization_distance_m = 0.5e-6 * C * self.rx_offset;
-            let max_numerization_distance_m = 0.5e-6 * C * (self.rx_offset + self.rx_duration - self.tx_offset - self.tx_duration); // Full resolution
-            geometry::plot(ui,
-                (0.0, self.carrier_height),
-                self.look_angle,
-                Some((min_aperture_elevation_angle_deg, max_aperture_elevation_angle_deg)),
-                Some((min_numerization_distance_m, max_numerization_distance_m)),
-            );
 
-            egui::Grid::new("rx_antenna_grid")
-                .num_columns(2)
-                .striped(true)
-                .spacing([20.0, 5.0])
-                .show(ui, |ui| {
-                    ui.label("Radar-Target distance:");
-                    ui.label(format!("{:.1} m", self.carrier_height / self.look_angle.to_radians().cos()));
-                    ui.end_row();
-                    ui.label("Ground illumination:");
-                    ui.label(format!(
-                        "from {:.1} m to {:.1} m",
-                        self.carrier_height * min_aperture_elevation_angle_deg.to_radians().tan(),
-                        self.carrier_height * max_aperture_elevation_angle_deg.to_radians().tan()
-                    ));
-                    ui.end_row();
-                });
+                egui::Grid::new("rx_antenna_grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .spacing([20.0, 5.0])
+                    .show(ui, |ui| {
+                        ui.label("Radar-Target distance:");
+                        ui.label(format!("{:.1} m", self.config.slant_range()));
+                        ui.end_row();
+                        ui.label("Incidence angle:");
+                        ui.label(format!("{:.3}°", self.config.incidence_angle(self.config.look_angle)));
+                        ui.end_row();
+                        ui.label("Ground illumination:");
+                        let (near, far) = self.config.ground_illumination();
+                        ui.label(format!("from {:.1} m to {:.1} m", near, far));
+                        ui.end_row();
+                    });
+
+                egui::CollapsingHeader::new("Resolution")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        let ground_range = resolution::ground_range_resolution_profile(&self.config, 101);
+                        // The coarsest azimuth resolution of the agility steps, at the longest wavelength.
+                        let (_, longest) = self.config.wavelength_extremes();
+                        let azimuth = resolution::azimuth_resolution_profile(&longest, 101);
+                        egui::Grid::new("resolution_grid")
+                            .num_columns(2)
+                            .striped(true)
+                            .spacing([20.0, 5.0])
+                            .show(ui, |ui| {
+                                ui.label("Integration Length:");
+                                ui.add(
+                                    egui::DragValue::new(&mut self.config.integration_length)
+                                        .fixed_decimals(1)
+                                        .range(0.0..=f64::NAN)
+                                        .suffix(" m")
+                                );
+                                ui.end_row();
+                                ui.label("");
+                                ui.label(format!(
+                                    "{:.3} s",
+                                    resolution::integration_duration(&self.config, self.config.look_angle)
+                                ));
+                                ui.end_row();
+                                ui.label("Slant range resolution:");
+                                let slant_range = resolution::slant_range_resolution(&self.config);
+                                if slant_range.is_finite() {
+                                    ui.label(format!("{:.3} m", slant_range));
+                                } else {
+                                    ui.label("n/a (needs bandwidth)");
+                                }
+                                ui.end_row();
+                                ui.label("Ground range resolution:");
+                                if let (Some(first), Some(last)) = (ground_range.first(), ground_range.last()) {
+                                    ui.label(format!("from {:.3} m to {:.3} m", first[1], last[1]));
+                                } else {
+                                    ui.label("n/a (needs bandwidth)");
+                                }
+                                ui.end_row();
+                                ui.label("Azimuth resolution:");
+                                if let (Some(first), Some(last)) = (azimuth.first(), azimuth.last()) {
+                                    if self.config.nb_agilities > 1 {
+                                        ui.label(format!("from {:.3} m to {:.3} m (at {:.4} GHz)", first[1], last[1], longest.center_frequency));
+                                    } else {
+                                        ui.label(format!("from {:.3} m to {:.3} m", first[1], last[1]));
+                                    }
+                                } else {
+                                    ui.label("n/a (needs frequency and azimuth aperture or integration length)");
+                                }
+                                ui.end_row();
+                            });
+                        profile::plot(ui, "Resolution", "m", vec![
+                            profile::Curve { name: "Ground range".to_string(), points: ground_range, color: egui::Color32::LIGHT_BLUE },
+                            profile::Curve { name: "Azimuth".to_string(), points: azimuth, color: egui::Color32::LIGHT_GREEN },
+                        ]);
+                    });
+
+                egui::CollapsingHeader::new("Sensitivity")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        // One profile per agility step, whose wavelength differs.
+                        let steps = self.config.agility_steps();
+                        let nesz: Vec<Vec<[f64; 2]>> = steps.iter()
+                            .map(|step| sensitivity::nesz_profile(step, 101))
+                            .collect();
+                        egui::Grid::new("sensitivity_grid")
+                            .num_columns(2)
+                            .striped(true)
+                            .spacing([20.0, 5.0])
+                            .show(ui, |ui| {
+                                ui.label("NESZ:");
+                                let min = nesz.iter().flatten().min_by(|a, b| a[1].total_cmp(&b[1]));
+                                let max = nesz.iter().flatten().max_by(|a, b| a[1].total_cmp(&b[1]));
+                                if let (Some(min), Some(max)) = (min, max) {
+                                    ui.label(format!(
+                                        "from {:.1} dB (at {:.1} m) to {:.1} dB (at {:.1} m)",
+                                        min[1], min[0], max[1], max[0]
+                                    ));
+                                } else {
+                                    ui.label("n/a (needs peak power, frequency, bandwidth and azimuth aperture)");
+                                }
+                                ui.end_row();
+                            });
+                        let curves = if nesz.len() > 1 {
+                            nesz.into_iter()
+                                .zip(&steps)
+                                .enumerate()
+                                .map(|(i, (points, step))| profile::Curve {
+                                    name: format!("NESZ F{} ({:.4} GHz)", i, step.center_frequency),
+                                    points,
+                                    color: egui::Color32::from_rgb(100, 100 + (155 * i / steps.len()) as u8, 255),
+                                })
+                                .collect()
+                        } else {
+                            vec![profile::Curve {
+                                name: "NESZ".to_string(),
+                                points: nesz.into_iter().next().unwrap_or_default(),
+                                color: egui::Color32::LIGHT_BLUE,
+                            }]
+                        };
+                        profile::plot(ui, "NESZ", "dB", curves);
+                    });
+
+                egui::CollapsingHeader::new("Level budget")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        // The echo is the strongest at the longest wavelength of the agility steps
+                        // and the weakest at the shortest one, both checked against the ADC.
+                        let (shortest, longest) = self.config.wavelength_extremes();
+                        let adc = level::adc_level_profile(&longest, 101);
+                        let weakest = if self.config.nb_agilities > 1 { level::adc_level_profile(&shortest, 101) } else { Vec::new() };
+                        let checked: Vec<[f64; 2]> = adc.iter().chain(&weakest).copied().collect();
+                        egui::Grid::new("level_grid")
+                            .num_columns(2)
+                            .striped(true)
+                            .spacing([20.0, 5.0])
+                            .show(ui, |ui| {
+                                if let (Some(first), Some(last)) = (adc.first(), adc.last()) {
+                                    ui.label("Received power:");
+                                    ui.label(format!(
+                                        "from {:.1} dBm to {:.1} dBm",
+                                        first[1] - self.config.rx_gain,
+                                        last[1] - self.config.rx_gain
+                                    ));
+                                    ui.end_row();
+                                    ui.label("ADC input level:");
+                                    ui.label(format!("from {:.1} dBm to {:.1} dBm", first[1], last[1]));
+                                    if let (Some(first), Some(last)) = (weakest.first(), weakest.last()) {
+                                        ui.end_row();
+                                        ui.label(format!("At {:.4} GHz:", shortest.center_frequency));
+                                        ui.label(format!("from {:.1} dBm to {:.1} dBm", first[1], last[1]));
+                                    }
+                                } else {
+                                    ui.label("ADC input level:");
+                                    ui.label("n/a (needs peak power, frequency and azimuth aperture)");
+                                }
+                                ui.end_row();
+                                ui.label("Thermal noise at ADC:");
+                                ui.label(format!("{:.1} dBm", level::thermal_noise_level(&self.config)));
+                                ui.end_row();
+                            });
+                        for warning in level::warnings(&self.config, &checked) {
+                            ui.colored_label(egui::Color32::ORANGE, format!("⚠ {}", warning));
+                        }
+                        // Channels with their own gain see a shifted ADC input level.
+                        for (i, channel) in self.config.channels.iter().enumerate() {
+                            if !channel.enabled || channel.gain == 0.0 {
+                                continue;
+                            }
+                            let shifted: Vec<[f64; 2]> = checked.iter().map(|p| [p[0], p[1] + channel.gain]).collect();
+                            for warning in level::warnings(&self.config, &shifted) {
+                                ui.colored_label(egui::Color32::ORANGE, format!("⚠ channel {}: {}", i, warning));
+                            }
+                        }
+                        let edges: Vec<f64> = adc.first().zip(adc.last()).map(|(a, b)| vec![a[0], b[0]]).unwrap_or_default();
+                        let mut curves = vec![
+                            profile::Curve { name: "ADC input level".to_string(), points: adc, color: egui::Color32::LIGHT_GREEN },
+                            profile::Curve {
+                                name: "ADC full scale".to_string(),
+                                points: edges.iter().map(|&x| [x, self.config.adc_full_scale]).collect(),
+                                color: egui::Color32::RED,
+                            },
+                            profile::Curve {
+                                name: "ADC noise floor".to_string(),
+                                points: edges.iter().map(|&x| [x, self.config.adc_noise_floor]).collect(),
+                                color: egui::Color32::GRAY,
+                            },
+                        ];
+                        if !weakest.is_empty() {
+                            curves.push(profile::Curve {
+                                name: format!("ADC input level ({:.4} GHz)", shortest.center_frequency),
+                                points: weakest,
+                                color: egui::Color32::DARK_GREEN,
+                            });
+                        }
+                        profile::plot(ui, "Level budget", "dBm", curves);
+                    });
+
+                egui::CollapsingHeader::new("Interferometry")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        // The largest baseline and height of ambiguity of the agility steps, at the longest wavelength.
+                        let (_, longest) = self.config.wavelength_extremes();
+                        egui::Grid::new("interferometry_grid")
+                            .num_columns(2)
+                            .striped(true)
+                            .spacing([20.0, 5.0])
+                            .show(ui, |ui| {
+                                ui.label("Target Height of Ambiguity:");
+                                ui.add(
+                                    egui::DragValue::new(&mut self.config.height_ambiguity)
+                                        .fixed_decimals(1)
+                                        .range(0.0..=f64::NAN)
+                                        .suffix(" m")
+                                );
+                                ui.end_row();
+                                ui.label("Perpendicular Baseline:");
+                                ui.add(
+                                    egui::DragValue::new(&mut self.config.perpendicular_baseline)
+                                        .fixed_decimals(3)
+                                        .range(0.0..=f64::NAN)
+                                        .suffix(" m")
+                                );
+                                ui.end_row();
+                                ui.label("Phase Noise:");
+                                ui.add(
+                                    egui::DragValue::new(&mut self.config.phase_noise)
+                                        .fixed_decimals(1)
+                                        .range(0.0..=180.0)
+                                        .suffix("°")
+                                );
+                                ui.end_row();
+                                ui.label("Required Height Accuracy:");
+                                ui.add(
+                                    egui::DragValue::new(&mut self.config.accuracy_height_ambiguity)
+                                        .fixed_decimals(2)
+                                        .range(0.0..=f64::NAN)
+                                        .suffix(" m")
+                                );
+                                ui.end_row();
+
+                                let look_angle = self.config.look_angle;
+                                ui.label("Required baseline (scene center):");
+                                let baseline = interferometry::required_baseline(&longest, look_angle, self.config.height_ambiguity);
+                                ui.label(if baseline.is_finite() { format!("{:.3} m", baseline) } else { "n/a".to_string() });
+                                ui.end_row();
+                                ui.label("Height of ambiguity (scene center):");
+                                let height_ambiguity = interferometry::height_of_ambiguity(&longest, look_angle, self.config.perpendicular_baseline);
+                                if height_ambiguity.is_finite() {
+                                    ui.label(format!("{:.2} m", height_ambiguity));
+                                    ui.end_row();
+                                    ui.label("Height accuracy (scene center):");
+                                    let accuracy = interferometry::height_accuracy(height_ambiguity, self.config.phase_noise);
+                                    let color = if accuracy > self.config.accuracy_height_ambiguity && self.config.accuracy_height_ambiguity > 0.0 {
+                                        egui::Color32::ORANGE
+                                    } else {
+                                        ui.visuals().text_color()
+                                    };
+                                    ui.colored_label(color, format!("{:.3} m", accuracy));
+                                } else {
+                                    ui.label("n/a");
+                                }
+                                ui.end_row();
+                            });
+                        profile::plot(ui, "Required baseline", "m", vec![
+                            profile::Curve {
+                                name: "Required baseline".to_string(),
+                                points: interferometry::required_baseline_profile(&longest, 101),
+                                color: egui::Color32::LIGHT_BLUE,
+                            },
+                        ]);
+                        let height_ambiguity = interferometry::height_of_ambiguity_profile(&longest, 101);
+                        let accuracy = height_ambiguity.iter()
+                            .map(|&[x, h]| [x, interferometry::height_accuracy(h, self.config.phase_noise)])
+                            .collect();
+                        profile::plot(ui, "Height of ambiguity", "m", vec![
+                            profile::Curve { name: "Height of ambiguity".to_string(), points: height_ambiguity, color: egui::Color32::LIGHT_GREEN },
+                            profile::Curve { name: "Height accuracy".to_string(), points: accuracy, color: egui::Color32::GOLD },
+                        ]);
+                    });
+
+                egui::CollapsingHeader::new("PRF diagram")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.label("Click in the diagram to set the PRI and the look angle.");
+                        if let Some([look_angle, prf]) = diagram::plot(ui, &self.config) && prf > 0.0 {
+                            // Bounded as in a JSON file, so that the configuration can be saved and reloaded.
+                            self.config.pri = (1e6 / prf).max(config::MIN_PRI);
+                            self.config.look_angle = look_angle.clamp(0.0, 90.0);
+                        }
+                    });
+
+                egui::CollapsingHeader::new("Blind ranges")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        let (near, far) = pri::swath_ranges(&self.config);
+                        let max_range = if far.is_finite() { 1.5 * far } else { pri::delay_to_range(4.0 * self.config.mean_pri()) };
+                        let eclipsed: Vec<[f64; 2]> = pri::eclipsed_profile(&self.config, max_range, 1001)
+                            .into_iter()
+                            .map(|[range, fraction]| [range, 100.0 * fraction])
+                            .collect();
+                        let mut blind: Vec<String> = pri::blind_ranges(&self.config, near, far)
+                            .into_iter()
+                            .map(|(start, end)| format!("{:.0} m to {:.0} m", start, end))
+                            .collect();
+                        if blind.len() == pri::MAX_BLIND_RANGES {
+                            blind.push("…".to_string());
+                        }
+                        egui::Grid::new("blind_range_grid")
+                            .num_columns(2)
+                            .striped(true)
+                            .spacing([20.0, 5.0])
+                            .show(ui, |ui| {
+                                ui.label("Mean PRF:");
+                                ui.label(format!("{:.1} Hz", self.config.mean_prf()));
+                                ui.end_row();
+                                ui.label("Blind ranges in the swath:");
+                                if blind.is_empty() {
+                                    ui.label("none");
+                                } else {
+                                    ui.label(blind.join(", "));
+                                }
+                                ui.end_row();
+                            });
+                        let mut curves = vec![
+                            profile::Curve { name: "Eclipsed pulses".to_string(), points: eclipsed, color: egui::Color32::LIGHT_RED },
+                        ];
+                        if far.is_finite() {
+                            curves.push(profile::Curve {
+                                name: "Swath".to_string(),
+                                points: vec![[near, 0.0], [near, 100.0], [far, 100.0], [far, 0.0]],
+                                color: egui::Color32::GRAY,
+                            });
+                        }
+                        profile::plot(ui, "Blind ranges", "%", curves);
+                    });
+
+                egui::CollapsingHeader::new("Ambiguities")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        let rasr = ambiguity::rasr_profile(&self.config, 101);
+                        // Worst agility step.
+                        let aasr = self.config.agility_steps().iter().map(ambiguity::aasr).fold(f64::NAN, f64::max);
+                        egui::Grid::new("ambiguity_grid")
+                            .num_columns(2)
+                            .striped(true)
+                            .spacing([20.0, 5.0])
+                            .show(ui, |ui| {
+                                ui.label("RASR:");
+                                match rasr.iter().max_by(|a, b| a[1].total_cmp(&b[1])) {
+                                    Some(worst) => ui.label(format!("up to {:.1} dB (at {:.1} m)", worst[1], worst[0])),
+                                    None => ui.label("negligible"),
+                                };
+                                ui.end_row();
+                                ui.label("AASR:");
+                                if aasr.is_nan() {
+                                    ui.label("n/a (needs frequency and azimuth aperture)");
+                                } else if aasr == f64::NEG_INFINITY {
+                                    ui.label("negligible");
+                                } else {
+                                    ui.label(format!("{:.1} dB", aasr));
+                                }
+                                ui.end_row();
+                                ui.label("Range ambiguities (scene center):");
+                                ui.vertical(|ui| {
+                                    for a in ambiguity::range_ambiguities(&self.config, self.config.look_angle) {
+                                        ui.label(format!("PRI {:+}: {:.1} m, {:.1} dB", a.order, a.ground_range, a.relative_power));
+                                    }
+                                });
+                                ui.end_row();
+                            });
+                        let edges: Vec<f64> = rasr.first().zip(rasr.last()).map(|(a, b)| vec![a[0], b[0]]).unwrap_or_default();
+                        let mut curves = vec![
+                            profile::Curve { name: "RASR".to_string(), points: rasr, color: egui::Color32::LIGHT_RED },
+                        ];
+                        if aasr.is_finite() {
+                            curves.push(profile::Curve {
+                                name: "AASR".to_string(),
+                                points: edges.iter().map(|&x| [x, aasr]).collect(),
+                                color: egui::Color32::LIGHT_BLUE,
+                            });
+                        }
+                        profile::plot(ui, "Ambiguities", "dB", curves);
+                    });
+            });
         });
+
+        // A drag or a value being typed is recorded once finished, as a single step.
+        if !ctx.input(|input| input.pointer.any_down()) && !ctx.wants_keyboard_input() {
+            self.history.record(&self.config);
+        }
     }
 }
 
@@ -0,0 +1,186 @@
+// PAMELA waveform files start with a text header of `KEY value` lines ended by
+// `END_HEADER`, followed by the interleaved I/Q samples as little-endian f32.
+use std::f64::consts::PI;
+use std::fmt::Write;
+use crate::config::RadarConfig;
+use crate::import::{self, Imported, Mapping};
+
+const MAGIC: &str = "PAMELA_WAVE 1";
+const END_HEADER: &str = "END_HEADER\n";
+// Samples of a chirp at most, 128 MB of I/Q data.
+const MAX_SAMPLES: f64 = (1 << 24) as f64;
+
+pub struct Wave {
+    pub name: String,
+    pub sample_rate: f64, // Hz
+    pub bandwidth: f64, // Hz
+    pub duration: f64, // s
+    pub samples: Vec<[f32; 2]>,
+}
+
+impl Wave {
+    // Baseband linear up-chirp sweeping from -B/2 to +B/2 over the pulse duration.
+    pub fn chirp(config: &RadarConfig) -> Result<Self, String> {
+        let sample_rate = config.fech * 1e6;
+        let bandwidth = config.bandwidth * 1e6;
+        let duration = config.tx_duration / 1e6;
+        if sample_rate <= 0.0 {
+            return Err("PAMELA export requires a sampling frequency (Fech I/O) above 0 MHz".to_string());
+        }
+        if bandwidth > sample_rate {
+            return Err(format!(
+                "the chirp bandwidth ({} MHz) exceeds the I/Q sampling frequency ({} MHz)",
+                config.bandwidth, config.fech
+            ));
+        }
+
+        let nb_samples = (duration * sample_rate).round();
+        if !(1.0..=MAX_SAMPLES).contains(&nb_samples) {
+            return Err(format!(
+                "a {} µs pulse sampled at {} MHz gives {} samples, expected 1 to {}",
+                config.tx_duration, config.fech, nb_samples, MAX_SAMPLES
+            ));
+        }
+        let nb_samples = nb_samples as usize;
+        let rate = bandwidth / duration;
+        let samples = (0..nb_samples)
+            .map(|n| {
+                let t = n as f64 / sample_rate - duration / 2.0;
+                let phase = PI * rate * t * t;
+                [phase.cos() as f32, phase.sin() as f32]
+            })
+            .collect();
+        Ok(Self { name: config.config_name.clone(), sample_rate, bandwidth, duration, samples })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut header = String::new();
+        writeln!(header, "{}", MAGIC).unwrap();
+        writeln!(header, "NAME {}", self.name).unwrap();
+        writeln!(header, "SAMPLE_RATE_HZ {}", self.sample_rate).unwrap();
+        writeln!(header, "BANDWIDTH_HZ {}", self.bandwidth).unwrap();
+        writeln!(header, "DURATION_S {}", self.duration).unwrap();
+        writeln!(header, "NB_SAMPLES {}", self.samples.len()).unwrap();
+        writeln!(header, "FORMAT IQ_F32_LE").unwrap();
+        header.push_str(END_HEADER);
+
+        let mut bytes = header.into_bytes();
+        for [i, q] in &self.samples {
+            bytes.extend_from_slice(&i.to_le_bytes());
+            bytes.extend_from_slice(&q.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let end = bytes.windows(END_HEADER.len())
+            .position(|w| w == END_HEADER.as_bytes())
+            .ok_or("missing END_HEADER")?;
+        let header = std::str::from_utf8(&bytes[..end]).map_err(|err| err.to_string())?;
+        let mut lines = header.lines();
+        if lines.next() != Some(MAGIC) {
+            return Err(format!("not a PAMELA wave file (expected \"{}\")", MAGIC));
+        }
+
+        let mut wave = Self { name: String::new(), sample_rate: 0.0, bandwidth: 0.0, duration: 0.0, samples: Vec::new() };
+        let mut nb_samples = None;
+        for line in lines {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let number = || value.parse::<f64>().map_err(|_| format!("{}: invalid number \"{}\"", key, value));
+            match key {
+                "NAME" => wave.name = value.to_string(),
+                "SAMPLE_RATE_HZ" => wave.sample_rate = number()?,
+                "BANDWIDTH_HZ" => wave.bandwidth = number()?,
+                "DURATION_S" => wave.duration = number()?,
+                "NB_SAMPLES" => nb_samples = Some(value.parse::<usize>().map_err(|_| format!("NB_SAMPLES: invalid count \"{}\"", value))?),
+                "FORMAT" if value == "IQ_F32_LE" => {}
+                "FORMAT" => return Err(format!("FORMAT: unsupported sample format \"{}\"", value)),
+                _ => return Err(format!("unknown header key \"{}\"", key)),
+            }
+        }
+        let nb_samples = nb_samples.ok_or("NB_SAMPLES: missing")?;
+
+        let data = &bytes[end + END_HEADER.len()..];
+        let nb_bytes = nb_samples.checked_mul(8).ok_or_else(|| format!("NB_SAMPLES: {} samples is too many", nb_samples))?;
+        if data.len() != nb_bytes {
+            return Err(format!("expected {} samples, found {} bytes of data", nb_samples, data.len()));
+        }
+        wave.samples = data.chunks_exact(8)
+            .map(|c| [
+                f32::from_le_bytes([c[0], c[1], c[2], c[3]]),
+                f32::from_le_bytes([c[4], c[5], c[6], c[7]]),
+            ])
+            .collect();
+        Ok(wave)
+    }
+}
+
+pub fn to_bytes(config: &RadarConfig) -> Result<Vec<u8>, String> {
+    Wave::chirp(config).map(|wave| wave.to_bytes())
+}
+
+const MAPPINGS: &[Mapping] = &[
+    Mapping { key: "SAMPLE_RATE_HZ", field: "rx.fech", scale: 1e-6 },
+    Mapping { key: "BANDWIDTH_HZ", field: "sensitivity.bandwidth", scale: 1e-6 },
+    Mapping { key: "DURATION_S", field: "tx.duration", scale: 1e6 },
+];
+
+pub fn from_bytes(bytes: &[u8]) -> Imported {
+    let wave = Wave::from_bytes(bytes).map_err(|err| vec![err])?;
+    let entries = vec![
+        ("SAMPLE_RATE_HZ".to_string(), wave.sample_rate),
+        ("BANDWIDTH_HZ".to_string(), wave.bandwidth),
+        ("DURATION_S".to_string(), wave.duration),
+    ];
+    import::apply("PAMELA", Some(wave.name), entries, MAPPINGS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let config = RadarConfig {
+            fech: 125.0,
+            bandwidth: 100.0,
+            tx_duration: 10.0,
+            ..RadarConfig::default()
+        };
+        let wave = Wave::from_bytes(&to_bytes(&config).unwrap()).unwrap();
+        assert_eq!(wave.samples.len(), 1250);
+        assert_eq!(wave.sample_rate, 125e6);
+
+        // The phase step between consecutive samples gives the instantaneous
+        // frequency halfway between them, which must follow the chirp rate.
+        let rate = wave.bandwidth / wave.duration;
+        for n in (0..wave.samples.len() - 1).step_by(50) {
+            let [i0, q0] = wave.samples[n];
+            let [i1, q1] = wave.samples[n + 1];
+            let step = (q1 as f64).atan2(i1 as f64) - (q0 as f64).atan2(i0 as f64);
+            let step = (step + PI).rem_euclid(2.0 * PI) - PI;
+            let frequency = step / (2.0 * PI) * wave.sample_rate;
+            let t = (n as f64 + 0.5) / wave.sample_rate - wave.duration / 2.0;
+            assert!((frequency - rate * t).abs() < 1e3, "sample {}: {} Hz instead of {} Hz", n, frequency, rate * t);
+        }
+    }
+
+    #[test]
+    fn sample_count_bounds() {
+        let error = |tx_duration: f64| Wave::chirp(&RadarConfig { fech: 100.0, tx_duration, ..RadarConfig::default() }).err().unwrap();
+        assert_eq!(error(1e9), "a 1000000000 µs pulse sampled at 100 MHz gives 100000000000 samples, expected 1 to 16777216");
+        assert!(error(0.0).contains("gives 0 samples"));
+        assert!(error(-10.0).contains("gives -1000 samples"));
+        assert!(error(f64::NAN).contains("gives NaN samples"));
+    }
+
+    #[test]
+    fn malformed_headers() {
+        let header = |nb_samples: &str| format!("{}\nNB_SAMPLES {}\nFORMAT IQ_F32_LE\n{}", MAGIC, nb_samples, END_HEADER);
+        let error = |bytes: &[u8]| Wave::from_bytes(bytes).err().unwrap();
+        assert_eq!(error(header(&usize::MAX.to_string()).as_bytes()), format!("NB_SAMPLES: {} samples is too many", usize::MAX));
+        assert_eq!(error(header("2").as_bytes()), "expected 2 samples, found 0 bytes of data");
+        assert_eq!(error(header("-1").as_bytes()), "NB_SAMPLES: invalid count \"-1\"");
+        assert_eq!(error(b"PAMELA_WAVE 2\nEND_HEADER\n"), "not a PAMELA wave file (expected \"PAMELA_WAVE 1\")");
+    }
+}
@@ -0,0 +1,80 @@
+use eframe::egui::{Color32, Ui, Vec2b};
+use egui_plot::{Corner, Legend, Line, Plot, PlotUi, Points};
+use sarconf::figure::{Bounds, Figure, Style};
+
+fn color([r, g, b]: [u8; 3]) -> Color32 {
+    Color32::from_rgb(r, g, b)
+}
+
+fn draw(plot_ui: &mut PlotUi, figure: &Figure) {
+    for series in &figure.series {
+        if series.marker {
+            plot_ui.points(
+                Points::new(series.name.clone(), series.points.clone())
+                    .radius(4.0)
+                    .color(color(series.color))
+            );
+            continue;
+        }
+        let mut line = Line::new(series.name.clone(), series.points.clone())
+            .color(color(series.color))
+            .width(series.width);
+        if series.style == Style::Dashed {
+            line = line.style(egui_plot::LineStyle::Dashed { length: 5.0 });
+        }
+        if let Some((base, alpha)) = series.fill {
+            line = line.fill(base as f32).fill_alpha(alpha);
+        }
+        if series.highlighted {
+            line = line.highlight(true);
+        }
+        plot_ui.line(line);
+    }
+}
+
+fn bounds(plot_ui: &PlotUi) -> Bounds {
+    let bounds = plot_ui.plot_bounds();
+    [bounds.min(), bounds.max()]
+}
+
+// Draws the geometry figure and returns the displayed bounds.
+pub fn geometry(ui: &mut Ui, figure: &Figure) -> Bounds {
+    let (x_unit, y_unit) = (figure.x_unit, figure.y_unit);
+    Plot::new("Geometry")
+        .data_aspect(1.0)
+        .height(300.0)
+        .legend(Legend::default().position(Corner::RightTop))
+        .x_axis_formatter(move |x, _| format!("{:.1} {}", x.value, x_unit))
+        .y_axis_formatter(move |y, _| format!("{:.1} {}", y.value, y_unit))
+        .show(ui, |plot_ui| {
+            draw(plot_ui, figure);
+            bounds(plot_ui)
+        })
+        .inner
+}
+
+// Draws the chronogram figure, which only pans and zooms in time, and returns the displayed bounds.
+pub fn chronogram(ui: &mut Ui, figure: &Figure) -> Bounds {
+    let [bottom, top] = figure.y_bounds.unwrap_or([0.0, 1.1]);
+    let x_unit = figure.x_unit;
+    let mut plot = Plot::new("Chronogram")
+        .height((100.0 * (top - bottom) / 1.1) as f32)
+        .show_y(false)
+        .allow_boxed_zoom(false)
+        .allow_drag(Vec2b::new(true, false))
+        .allow_zoom(Vec2b::new(true, false))
+        .allow_scroll(Vec2b::new(true, false))
+        .show_axes(Vec2b::new(true, false))
+        .show_grid(Vec2b::new(true, false))
+        .default_y_bounds(bottom, top)
+        .x_axis_formatter(move |x, _| format!("{:.1} {}", x.value, x_unit))
+        .legend(Legend::default().position(Corner::RightBottom));
+    for &x in &figure.x_include {
+        plot = plot.include_x(x);
+    }
+    plot.show(ui, |plot_ui| {
+        draw(plot_ui, figure);
+        bounds(plot_ui)
+    })
+    .inner
+}
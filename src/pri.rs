@@ -0,0 +1,307 @@
+// Non-uniform PRI sequences. Staggering the PRI moves the ranges whose echo
+// comes back while a later pulse is transmitted (blind ranges) from one pulse to
+// the next, so that no range is eclipsed for every pulse of the sequence.
+use crate::config::{C, RadarConfig};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PriMode {
+    Constant,
+    Staggered,
+    Jittered,
+    List,
+}
+
+impl PriMode {
+    pub const ALL: [PriMode; 4] = [PriMode::Constant, PriMode::Staggered, PriMode::Jittered, PriMode::List];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PriMode::Constant => "Constant",
+            PriMode::Staggered => "Staggered",
+            PriMode::Jittered => "Jittered",
+            PriMode::List => "List",
+        }
+    }
+
+    // Identifier used in the configuration files.
+    pub fn key(self) -> &'static str {
+        match self {
+            PriMode::Constant => "constant",
+            PriMode::Staggered => "staggered",
+            PriMode::Jittered => "jittered",
+            PriMode::List => "list",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<PriMode> {
+        PriMode::ALL.into_iter().find(|m| m.key() == key)
+    }
+}
+
+// Reproducible pseudo-random deviation in [-1, 1] of the PRI of pulse `index`
+// (SplitMix64), so that a jittered sequence is the same on every run.
+pub fn jitter(index: usize) -> f64 {
+    let mut z = (index as u64 + 1).wrapping_mul(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^= z >> 31;
+    2.0 * (z >> 11) as f64 / (1u64 << 53) as f64 - 1.0
+}
+
+// Start times of `nb_pulses` consecutive pulses cycling through `sequence`, in µs.
+pub fn pulse_times(sequence: &[f64], nb_pulses: usize) -> Vec<f64> {
+    let mut time = 0.0;
+    (0..nb_pulses)
+        .map(|i| {
+            let start = time;
+            time += sequence[i % sequence.len()];
+            start
+        })
+        .collect()
+}
+
+// Times of the pulses of the sequence relative to each of them, over one period
+// of the sequence: the later pulses an echo may meet are found modulo the period.
+pub struct Eclipses {
+    offsets: Vec<Vec<f64>>, // µs from pulse i to the pulses i to i + N - 1
+    period: f64, // µs
+    tau: f64, // µs
+}
+
+impl Eclipses {
+    pub fn new(config: &RadarConfig) -> Self {
+        let sequence = config.pri_sequence();
+        let n = sequence.len();
+        let offsets = (0..n)
+            .map(|i| {
+                let mut time = 0.0;
+                (0..n)
+                    .map(|m| {
+                        let offset = time;
+                        time += sequence[(i + m) % n];
+                        offset
+                    })
+                    .collect()
+            })
+            .collect();
+        Self { offsets, period: sequence.iter().sum(), tau: config.tx_duration }
+    }
+
+    // Tells whether the echo of pulse `pulse`, coming back `delay` µs after it
+    // starts, meets the transmission of a later pulse. Pulses being at least a
+    // pulse duration apart, only the later pulses just before and just after the
+    // echo can meet it.
+    fn eclipsed(&self, pulse: usize, delay: f64) -> bool {
+        let offsets = &self.offsets[pulse];
+        let start = (delay / self.period).floor() * self.period;
+        let next = offsets.partition_point(|&offset| offset <= delay - start);
+        let before = start + offsets[next.saturating_sub(1)];
+        let after = start + offsets.get(next).copied().unwrap_or(self.period);
+        [before, after].into_iter().any(|time| time > 0.0 && (time - delay).abs() < self.tau)
+    }
+
+    // Fraction of the pulses of the sequence whose echo, coming back `delay` µs
+    // after the pulse starts, meets the transmission of a later pulse.
+    pub fn fraction(&self, delay: f64) -> f64 {
+        let eclipsed = (0..self.offsets.len()).filter(|&i| self.eclipsed(i, delay)).count();
+        eclipsed as f64 / self.offsets.len() as f64
+    }
+
+    // Delays from `from` to `to` µs at which the echo of pulse `pulse` is
+    // eclipsed, as sorted `(start, end)` intervals in µs.
+    fn eclipsed_delays(&self, pulse: usize, from: f64, to: f64) -> Vec<(f64, f64)> {
+        let first = ((from - self.tau) / self.period).floor().max(0.0) as u64;
+        let last = ((to + self.tau) / self.period).ceil() as u64;
+        let mut intervals: Vec<(f64, f64)> = Vec::new();
+        for k in first..=last {
+            for offset in &self.offsets[pulse] {
+                let time = k as f64 * self.period + offset;
+                let (start, end) = ((time - self.tau).max(from), (time + self.tau).min(to));
+                if time <= 0.0 || start >= end {
+                    continue;
+                }
+                match intervals.last_mut() {
+                    Some(previous) if start <= previous.1 => previous.1 = previous.1.max(end),
+                    _ => intervals.push((start, end)),
+                }
+            }
+        }
+        intervals
+    }
+
+    // Delays from `from` to `to` µs at which the echo of every pulse is eclipsed.
+    fn blind_delays(&self, from: f64, to: f64) -> Vec<(f64, f64)> {
+        (1..self.offsets.len()).fold(self.eclipsed_delays(0, from, to), |blind, i| {
+            intersection(&blind, &self.eclipsed_delays(i, from, to))
+        })
+    }
+}
+
+// Intersection of two sorted lists of disjoint intervals.
+fn intersection(a: &[(f64, f64)], b: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let (mut i, mut j) = (0, 0);
+    let mut intervals = Vec::new();
+    while i < a.len() && j < b.len() {
+        let (start, end) = (a[i].0.max(b[j].0), a[i].1.min(b[j].1));
+        if start < end {
+            intervals.push((start, end));
+        }
+        if a[i].1 < b[j].1 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    intervals
+}
+
+// Fraction of the pulses of the sequence whose echo, coming back `delay` µs
+// after the pulse starts, meets the transmission of a later pulse.
+pub fn eclipsed_fraction(config: &RadarConfig, delay: f64) -> f64 {
+    Eclipses::new(config).fraction(delay)
+}
+
+// Equivalent monostatic range of an echo coming back `delay` µs after its pulse,
+// in m: half the range sum in bistatic configuration.
+pub fn delay_to_range(delay: f64) -> f64 {
+    C * delay * 1e-6 / 2.0
+}
+
+// Delay in µs of the echo from the equivalent monostatic range `range`, in m.
+pub fn range_to_delay(range: f64) -> f64 {
+    range * 2.0 / C * 1e6
+}
+
+// Equivalent monostatic ranges of the first and last echoes of the swath, in m.
+pub fn swath_ranges(config: &RadarConfig) -> (f64, f64) {
+    let (first, last) = config.swath_echo_times();
+    (delay_to_range(first - config.tx_offset), delay_to_range(last + config.tx_duration - config.tx_offset))
+}
+
+// Eclipsed fraction of the pulses from range 0 to `max_range`, as `[range (m), fraction]` points.
+pub fn eclipsed_profile(config: &RadarConfig, max_range: f64, nb_points: usize) -> Vec<[f64; 2]> {
+    let eclipses = Eclipses::new(config);
+    let max_delay = range_to_delay(max_range);
+    (0..nb_points)
+        .map(|i| {
+            let delay = max_delay * i as f64 / nb_points.saturating_sub(1).max(1) as f64;
+            [delay_to_range(delay), eclipses.fraction(delay)]
+        })
+        .collect()
+}
+
+pub const MAX_BLIND_RANGES: usize = 64;
+
+// First `MAX_BLIND_RANGES` ranges from `min_range` to `max_range` eclipsed for
+// every pulse of the sequence, as `(start, end)` intervals in m. After the first
+// period of the sequence the blind delays repeat with its period, so only the
+// first two periods are computed.
+pub fn blind_ranges(config: &RadarConfig, min_range: f64, max_range: f64) -> Vec<(f64, f64)> {
+    let eclipses = Eclipses::new(config);
+    let period = eclipses.period;
+    let (min_delay, max_delay) = (range_to_delay(min_range), range_to_delay(max_range));
+    let mut delays: Vec<(f64, f64)> = Vec::new();
+    let add = |delays: &mut Vec<(f64, f64)>, start: f64, end: f64| {
+        let (start, end) = (start.max(min_delay), end.min(max_delay));
+        if start >= end {
+            return;
+        }
+        // Join the intervals split at the end of a period.
+        match delays.last_mut() {
+            Some(previous) if start <= previous.1 + 1e-9 => previous.1 = end,
+            _ => delays.push((start, end)),
+        }
+    };
+    for (start, end) in eclipses.blind_delays(0.0, period) {
+        add(&mut delays, start, end);
+    }
+    let repeated = eclipses.blind_delays(period, 2.0 * period);
+    if !repeated.is_empty() {
+        let mut k = (min_delay / period).floor().max(1.0);
+        while k * period < max_delay && delays.len() <= MAX_BLIND_RANGES {
+            for &(start, end) in &repeated {
+                add(&mut delays, start + (k - 1.0) * period, end + (k - 1.0) * period);
+            }
+            k += 1.0;
+        }
+    }
+    delays.truncate(MAX_BLIND_RANGES);
+    delays.into_iter().map(|(start, end)| (delay_to_range(start), delay_to_range(end))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn staggering_removes_blind_ranges() {
+        let config = RadarConfig::default();
+        let blind = blind_ranges(&config, 0.0, 40e3);
+        // Echoes coming back one and two PRIs later, within a pulse duration.
+        assert_eq!(blind.len(), 2);
+        assert!((blind[0].0 - delay_to_range(90.0)).abs() < 1e-6);
+        assert!((blind[1].1 - delay_to_range(210.0)).abs() < 1e-6);
+
+        let config = RadarConfig { pri_mode: PriMode::Staggered, pri_steps: 3, pri_stagger: 25.0, ..config };
+        assert_eq!(config.pri_sequence(), [75.0, 100.0, 125.0]);
+        assert_eq!(config.mean_prf(), 10e3);
+        assert!(blind_ranges(&config, 0.0, 40e3).is_empty());
+        let fraction = eclipsed_fraction(&config, 100.0);
+        assert!(fraction > 0.0 && fraction < 1.0);
+    }
+
+    #[test]
+    fn jittered_sequence() {
+        let config = RadarConfig { pri_mode: PriMode::Jittered, pri_steps: 64, pri_jitter: 5.0, ..RadarConfig::default() };
+        let sequence = config.pri_sequence();
+        assert_eq!(sequence.len(), 64);
+        assert!(sequence.iter().all(|pri| (95.0..=105.0).contains(pri)));
+        assert_eq!(sequence, config.pri_sequence());
+        assert_eq!(pulse_times(&[10.0, 20.0], 4), [0.0, 10.0, 30.0, 40.0]);
+    }
+
+    #[test]
+    fn listed_sequence() {
+        let config = RadarConfig { pri_mode: PriMode::List, pri_list: vec![5.0, 100.0, 0.5], ..RadarConfig::default() };
+        assert_eq!(config.raw_pri_sequence(), [5.0, 100.0, 0.5]);
+        assert_eq!(config.pri_sequence(), [5.0, 100.0, 1.0]);
+    }
+
+    #[test]
+    fn periodic_eclipses() {
+        let config = RadarConfig { pri_mode: PriMode::Jittered, pri_steps: 16, pri_jitter: 20.0, tx_duration: 15.0, ..RadarConfig::default() };
+        let sequence = config.pri_sequence();
+        let eclipses = Eclipses::new(&config);
+        for delay in (0..5000).map(|d| d as f64 * 0.37) {
+            // Later pulses, until they start after the echo ends.
+            let expected = (0..sequence.len())
+                .filter(|&i| {
+                    let mut time = 0.0;
+                    (i..)
+                        .map(|j| {
+                            time += sequence[j % sequence.len()];
+                            time
+                        })
+                        .take_while(|&t| t < delay + config.tx_duration)
+                        .any(|t| t + config.tx_duration > delay)
+                })
+                .count();
+            assert_eq!(eclipses.fraction(delay), expected as f64 / sequence.len() as f64, "{} µs", delay);
+        }
+
+        // Blind ranges far from the radar are found in the repeated period.
+        let config = RadarConfig { pri_mode: PriMode::Staggered, pri_stagger: 5.0, tx_duration: 15.0, ..RadarConfig::default() };
+        let eclipses = Eclipses::new(&config);
+        let blind = blind_ranges(&config, 1e6, 1.1e6);
+        assert!(!blind.is_empty() && blind.len() <= MAX_BLIND_RANGES);
+        for (start, end) in blind {
+            assert!(start >= 1e6 && start < end && end <= 1.1e6);
+            assert_eq!(eclipses.fraction(range_to_delay((start + end) / 2.0)), 1.0);
+        }
+
+        // At a grazing look angle the swath spans hundreds of PRIs.
+        let config = RadarConfig { look_angle: 81.0, ..RadarConfig::default() };
+        let (near, far) = swath_ranges(&config);
+        assert_eq!(blind_ranges(&config, near, far).len(), MAX_BLIND_RANGES);
+    }
+}
@@ -0,0 +1,25 @@
+use eframe::egui::{Color32, Ui};
+use egui_plot::{Plot, Line, Legend, Corner};
+
+pub struct Curve {
+    pub name: String,
+    pub points: Vec<[f64; 2]>,
+    pub color: Color32,
+}
+
+// Plots quantities expressed in `unit` against the ground range across the swath.
+pub fn plot(ui: &mut Ui, id: &str, unit: &'static str, curves: Vec<Curve>) {
+    let legend = Legend::default().position(Corner::RightTop);
+
+    Plot::new(id)
+        .height(150.0)
+        .legend(legend)
+        .allow_scroll(false)
+        .x_axis_formatter(|x, _| format!("{:.1} m", x.value))
+        .y_axis_formatter(move |y, _| format!("{:.1} {}", y.value, unit))
+        .show(ui, |plot_ui| {
+            for curve in curves {
+                plot_ui.line(Line::new(curve.name, curve.points).color(curve.color).width(2.0));
+            }
+        });
+}
@@ -0,0 +1,78 @@
+use crate::bistatic;
+use crate::config::{C, RadarConfig};
+use crate::sensitivity::integration_time;
+
+// Slant range resolution after range compression, c / 2B, in m.
+pub fn slant_range_resolution(config: &RadarConfig) -> f64 {
+    C / (2.0 * config.bandwidth * 1e6)
+}
+
+// Ground range resolution at the point seen under `look_angle_deg`, in m:
+// c / (2 B sin θi) in a monostatic configuration.
+pub fn ground_range_resolution(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    C / (config.bandwidth * 1e6 * bistatic::ground_range_gradient(config, look_angle_deg))
+}
+
+// Duration of the stripmap integration at the point seen under `look_angle_deg`,
+// in s: the configured integration length flown by the transmitter, or the time
+// spent in the azimuth beam when it is 0.
+pub fn integration_duration(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    if config.integration_length > 0.0 {
+        config.integration_length / config.carrier_velocity
+    } else {
+        integration_time(config, config.slant_range_at(look_angle_deg))
+    }
+}
+
+// Azimuth resolution of the stripmap image at the point seen under
+// `look_angle_deg`, in m: λ R / (2 v Tint) in a monostatic configuration.
+pub fn azimuth_resolution(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    config.wavelength()
+        / (bistatic::angular_rate(config, look_angle_deg) * integration_duration(config, look_angle_deg))
+}
+
+// Ground range resolution across the swath, as `[ground range (m), resolution (m)]` points.
+pub fn ground_range_resolution_profile(config: &RadarConfig, nb_points: usize) -> Vec<[f64; 2]> {
+    config.swath_angles(nb_points)
+        .into_iter()
+        .map(|angle| [config.ground_range(angle), ground_range_resolution(config, angle)])
+        .filter(|[_, resolution]| resolution.is_finite())
+        .collect()
+}
+
+// Azimuth resolution across the swath, as `[ground range (m), resolution (m)]` points.
+pub fn azimuth_resolution_profile(config: &RadarConfig, nb_points: usize) -> Vec<[f64; 2]> {
+    config.swath_angles(nb_points)
+        .into_iter()
+        .map(|angle| [config.ground_range(angle), azimuth_resolution(config, angle)])
+        .filter(|[_, resolution]| resolution.is_finite())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monostatic_resolutions() {
+        let config = RadarConfig {
+            bandwidth: 150.0,
+            center_frequency: 10.0,
+            azimuth_aperture_angle: 2.0,
+            ..RadarConfig::default()
+        };
+        assert!((slant_range_resolution(&config) - 0.999308).abs() < 1e-6);
+        let ground = ground_range_resolution(&config, 30.0);
+        assert!((ground - slant_range_resolution(&config) / 0.5).abs() < 1e-9);
+
+        // The full synthetic aperture gives λ / 2θ whatever the range.
+        let full = config.wavelength() / (2.0 * 2f64.to_radians());
+        assert!((azimuth_resolution(&config, 30.0) - full).abs() < 1e-9);
+        assert!((azimuth_resolution(&config, 50.0) - full).abs() < 1e-9);
+
+        // A shorter integration degrades it proportionally to the range.
+        let config = RadarConfig { integration_length: 50.0, ..config };
+        let range = config.slant_range_at(30.0);
+        assert!((azimuth_resolution(&config, 30.0) - config.wavelength() * range / 100.0).abs() < 1e-9);
+    }
+}
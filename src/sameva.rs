@@ -0,0 +1,165 @@
+// SAMEVA init files are INI-like: `[SECTION]` headers followed by `KEY = value`
+// lines, with every value in SI units (m, m/s, rad, s, Hz) and `;` comments.
+use std::fmt::Write;
+use crate::config::RadarConfig;
+use crate::import::{self, Imported, Mapping};
+
+const MAPPINGS: &[Mapping] = &[
+    Mapping { key: "CARRIER.ALTITUDE", field: "carrier.height", scale: 1.0 },
+    Mapping { key: "CARRIER.VELOCITY", field: "carrier.velocity", scale: 1.0 },
+    Mapping { key: "CARRIER.LOOK_ANGLE", field: "carrier.look_angle", scale: 180.0 / std::f64::consts::PI },
+    Mapping { key: "ANTENNA.ELEVATION_APERTURE", field: "antenna.elevation_aperture_angle", scale: 180.0 / std::f64::consts::PI },
+    Mapping { key: "ANTENNA.AZIMUTH_APERTURE", field: "antenna.azimuth_aperture_angle", scale: 180.0 / std::f64::consts::PI },
+    Mapping { key: "TIMING.PRI", field: "tx.pri", scale: 1e6 },
+    Mapping { key: "TIMING.PULSE_OFFSET", field: "tx.offset", scale: 1e6 },
+    Mapping { key: "TIMING.PULSE_DURATION", field: "tx.duration", scale: 1e6 },
+    Mapping { key: "TIMING.RX_OFFSET", field: "rx.offset", scale: 1e6 },
+    Mapping { key: "TIMING.RX_DURATION", field: "rx.duration", scale: 1e6 },
+    Mapping { key: "TIMING.FECH", field: "rx.fech", scale: 1e-6 },
+    Mapping { key: "AGILITY.NB_STEPS", field: "tx.nb_agilities", scale: 1.0 },
+];
+
+fn entry(out: &mut String, key: &str, value: impl std::fmt::Display, unit: &str) {
+    writeln!(out, "{:<20} = {:<24} ; {}", key, value, unit).unwrap();
+}
+
+pub fn to_string(config: &RadarConfig) -> String {
+    let mut out = String::new();
+    writeln!(out, "; SAMEVA init file generated by SARConf").unwrap();
+    writeln!(out, "; Configuration: {}", config.config_name).unwrap();
+
+    writeln!(out, "\n[CARRIER]").unwrap();
+    entry(&mut out, "ALTITUDE", config.carrier_height, "m");
+    entry(&mut out, "VELOCITY", config.carrier_velocity, "m/s");
+    entry(&mut out, "LOOK_ANGLE", config.look_angle.to_radians(), "rad");
+
+    writeln!(out, "\n[ANTENNA]").unwrap();
+    entry(&mut out, "ELEVATION_APERTURE", config.elevation_aperture_angle.to_radians(), "rad");
+    entry(&mut out, "AZIMUTH_APERTURE", config.azimuth_aperture_angle.to_radians(), "rad");
+
+    writeln!(out, "\n[TIMING]").unwrap();
+    entry(&mut out, "PRI", config.pri / 1e6, "s");
+    entry(&mut out, "PULSE_OFFSET", config.tx_offset / 1e6, "s");
+    entry(&mut out, "PULSE_DURATION", config.tx_duration / 1e6, "s");
+    entry(&mut out, "RX_OFFSET", config.rx_offset / 1e6, "s");
+    entry(&mut out, "RX_DURATION", config.rx_duration / 1e6, "s");
+    entry(&mut out, "FECH", config.fech * 1e6, "Hz");
+
+    writeln!(out, "\n[AGILITY]").unwrap();
+    entry(&mut out, "NB_STEPS", config.nb_agilities, "count");
+    for (step, offset) in config.agility_offsets.iter().enumerate() {
+        entry(&mut out, &format!("OFFSET_{}", step), offset * 1e6, "Hz from the center frequency");
+    }
+    let sequence: Vec<String> = config.agility_sequence().iter().map(u32::to_string).collect();
+    entry(&mut out, "SEQUENCE", sequence.join(","), "steps in transmission order");
+    out
+}
+
+pub fn from_bytes(bytes: &[u8]) -> Imported {
+    let mut name = None;
+    let mut section = "";
+    let mut entries = Vec::new();
+    let mut offsets = Vec::new();
+    let mut sequence = None;
+    let mut errors = Vec::new();
+    for (number, line) in import::text(bytes)?.lines().enumerate() {
+        if let Some(config_name) = line.strip_prefix("; Configuration: ") {
+            name = Some(config_name.to_string());
+        }
+        let line = line.split(';').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = header.trim();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            errors.push(format!("line {}: expected `KEY = value`", number + 1));
+            continue;
+        };
+        let key = format!("{}.{}", section, key.trim());
+        if key == "AGILITY.SEQUENCE" {
+            match import::parse_sequence(value) {
+                Some(steps) => sequence = Some(steps),
+                None => errors.push(format!("line {}: invalid agility sequence \"{}\"", number + 1, value.trim())),
+            }
+            continue;
+        }
+        match value.trim().parse::<f64>() {
+            Ok(value) => match key.strip_prefix("AGILITY.OFFSET_").and_then(|step| step.parse().ok()) {
+                Some(step) => offsets.push((key, step, value / 1e6)),
+                None => entries.push((key, value)),
+            },
+            Err(_) => errors.push(format!("line {}: invalid number \"{}\"", number + 1, value.trim())),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let (mut config, mut warnings) = import::apply("SAMEVA", name, entries, MAPPINGS)?;
+    import::apply_agilities(&mut config, &mut warnings, offsets, sequence.map(|s| ("AGILITY.SEQUENCE", s)))?;
+    Ok((config, warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn golden_file() {
+        let config = RadarConfig {
+            config_name: "Golden".to_string(),
+            azimuth_aperture_angle: 10.0,
+            fech: 125.0,
+            ..RadarConfig::default()
+        };
+        assert_eq!(to_string(&config), include_str!("../tests/golden/sameva.init"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let config = RadarConfig {
+            config_name: "Round trip".to_string(),
+            carrier_height: 4500.0,
+            look_angle: 30.0,
+            azimuth_aperture_angle: 10.0,
+            pri: 125.0,
+            tx_duration: 12.5,
+            fech: 125.0,
+            ..RadarConfig::default()
+        };
+        let (imported, warnings) = from_bytes(to_string(&config).as_bytes()).unwrap();
+        assert_eq!(imported.config_name, "Round trip");
+        assert_eq!((imported.carrier_height, imported.pri, imported.tx_duration, imported.fech), (4500.0, 125.0, 12.5, 125.0));
+        assert!((imported.look_angle - 30.0).abs() < 1e-12);
+        assert!((imported.azimuth_aperture_angle - 10.0).abs() < 1e-12);
+        assert!(warnings.iter().all(|w| w.ends_with("not expressed in the SAMEVA file, default value kept")));
+    }
+
+    #[test]
+    fn malformed_lines() {
+        let errors = from_bytes(b"[TIMING]\nPRI 1e-4\nFECH = fast\n").err().unwrap();
+        assert_eq!(errors, ["line 2: expected `KEY = value`", "line 3: invalid number \"fast\""]);
+        let errors = from_bytes(b"[CARRIER]\nLOOK_ANGLE = 2\n").err().unwrap();
+        assert!(errors[0].starts_with("CARRIER.LOOK_ANGLE: 114.59"));
+        let (_, warnings) = from_bytes(b"[CARRIER]\nROLL = 0\n").unwrap();
+        assert_eq!(warnings[0], "CARRIER.ROLL: not modelled by SARConf, ignored");
+    }
+
+    #[test]
+    fn agility_plan_round_trip() {
+        let mut config = RadarConfig { center_frequency: 9.6, nb_agilities: 3, ..RadarConfig::default() };
+        config.resize_agilities();
+        config.agility_offsets = vec![0.0, 40.5, -40.5];
+        config.agility_sequence = vec![2, 0, 1];
+        let text = to_string(&config);
+        let (imported, _) = from_bytes(text.as_bytes()).unwrap();
+        assert_eq!(imported.nb_agilities, 3);
+        assert_eq!(imported.agility_offsets, config.agility_offsets);
+        assert_eq!(imported.agility_sequence, config.agility_sequence);
+
+        let errors = from_bytes(text.replace("2,0,1", "2,2,1").as_bytes()).err().unwrap();
+        assert!(errors[0].ends_with("must list each of the 3 agility steps once"));
+    }
+}
@@ -0,0 +1,76 @@
+use std::f64::consts::PI;
+use crate::config::RadarConfig;
+use crate::{bistatic, resolution};
+
+pub const BOLTZMANN: f64 = 1.380649e-23; // J/K
+pub const T0: f64 = 290.0; // Reference noise temperature in K
+
+pub fn to_db(x: f64) -> f64 {
+    10.0 * x.log10()
+}
+
+pub fn from_db(x: f64) -> f64 {
+    10f64.powf(x / 10.0)
+}
+
+// Time spent by a target in the azimuth beam at slant range `range_m`, in s.
+pub fn integration_time(config: &RadarConfig, range_m: f64) -> f64 {
+    range_m * config.azimuth_aperture_angle.to_radians() / config.carrier_velocity
+}
+
+// Noise equivalent sigma zero for a target seen under `look_angle_deg`, in dB.
+//
+// The SNR of a resolution cell after range compression (gain τ·B) and azimuth
+// integration (PRF·Tint pulses) is set to 1, giving
+// σ0 = (4π)³ Rt² Rr² k T0 F L / (Pt G² λ² τ PRF Tint δrg δaz)
+// with the ground range resolution δrg and the azimuth resolution δaz given by
+// `resolution`.
+pub fn nesz(config: &RadarConfig, look_angle_deg: f64) -> f64 {
+    let range = config.slant_range_at(look_angle_deg);
+    let rx_range = bistatic::receiver_range(config, look_angle_deg);
+    let wavelength = config.wavelength();
+    let tau = config.tx_duration * 1e-6;
+    let t_int = resolution::integration_duration(config, look_angle_deg);
+    let ground_range_resolution = resolution::ground_range_resolution(config, look_angle_deg);
+    let azimuth_resolution = resolution::azimuth_resolution(config, look_angle_deg);
+
+    let noise = (4.0 * PI).powi(3) * (range * rx_range).powi(2) * BOLTZMANN * T0
+        * from_db(config.noise_factor) * from_db(config.loss_power);
+    let signal = config.peak_power * from_db(2.0 * config.gain_antenna) * wavelength.powi(2)
+        * tau * config.final_prf() * t_int * ground_range_resolution * azimuth_resolution;
+    to_db(noise / signal)
+}
+
+// NESZ across the illuminated swath, as `[ground range (m), NESZ (dB)]` points.
+pub fn nesz_profile(config: &RadarConfig, nb_points: usize) -> Vec<[f64; 2]> {
+    config.swath_angles(nb_points)
+        .into_iter()
+        .map(|angle| [config.ground_range(angle), nesz(config, angle)])
+        .filter(|[_, nesz]| nesz.is_finite())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nesz_follows_range_cubed() {
+        let config = RadarConfig {
+            peak_power: 100.0,
+            loss_power: 3.0,
+            gain_antenna: 20.0,
+            noise_factor: 4.0,
+            center_frequency: 9.6,
+            bandwidth: 100.0,
+            azimuth_aperture_angle: 10.0,
+            ..RadarConfig::default()
+        };
+        // Tint grows with R, so NESZ ∝ R³ sin θi for a fixed height.
+        let ratio = |angle: f64| angle.to_radians().sin() / angle.to_radians().cos().powi(3);
+        let expected = to_db(ratio(60.0) / ratio(30.0));
+        assert!((nesz(&config, 60.0) - nesz(&config, 30.0) - expected).abs() < 1e-9);
+        assert_eq!(nesz_profile(&config, 11).len(), 11);
+        assert!(nesz_profile(&RadarConfig::default(), 11).is_empty());
+    }
+}
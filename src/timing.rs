@@ -0,0 +1,255 @@
+use std::fmt;
+use crate::config::RadarConfig;
+use crate::{genio, pri};
+
+// Names of the chronogram windows the rules refer to.
+pub const TX: &str = "TX";
+pub const RX: &str = "RX";
+pub const RX_FULL_RESOLUTION: &str = "RX (full resol)";
+pub const NOISE: &str = "Noise";
+pub const REINJ: &str = "Reinj";
+pub const NADIR: &str = "Nadir";
+pub const SWATH_ECHO: &str = "Swath echo";
+pub const DIRECT_PATH: &str = "Direct path";
+
+// Tells whether `a` intersects any repetition of `b` when both are repeated
+// after each pulse of the PRI sequence.
+pub fn overlaps_periodic(a: (f64, f64), b: (f64, f64), sequence: &[f64]) -> bool {
+    overlap_shift(a, b, sequence).is_some()
+}
+
+// Smallest number of pulses by which `b` must be shifted to intersect `a`, over
+// the pulses of one period of the PRI sequence.
+pub fn overlap_shift(a: (f64, f64), b: (f64, f64), sequence: &[f64]) -> Option<i64> {
+    if a.1 <= a.0 || b.1 <= b.0 || ![a.0, a.1, b.0, b.1].iter().all(|t| t.is_finite()) {
+        return None;
+    }
+    let n = sequence.len() as i64;
+    let times = pri::pulse_times(sequence, sequence.len() + 1);
+    let period = times[sequence.len()];
+    let time = |j: i64| times[j.rem_euclid(n) as usize] + j.div_euclid(n) as f64 * period;
+    (0..n)
+        .filter_map(|i| {
+            // First pulse j for which b ends after a starts.
+            let after = time(i) + a.0 - b.1;
+            let cycles = (after / period).floor();
+            let j = cycles as i64 * n + times[..sequence.len()].partition_point(|&t| t <= after - cycles * period) as i64;
+            (time(j) + b.0 < time(i) + a.1).then_some(j - i)
+        })
+        .min()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+pub struct Violation {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub windows: Vec<&'static str>, // Offending chronogram windows
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} [{}]", self.severity.name(), self.message, self.rule)
+    }
+}
+
+// Message and offending windows of a violated rule.
+pub type Finding = (String, Vec<&'static str>);
+
+pub struct Rule {
+    pub name: &'static str,
+    pub severity: Severity,
+    pub check: fn(&RadarConfig) -> Option<Finding>,
+}
+
+fn tx(config: &RadarConfig) -> (f64, f64) {
+    (config.tx_offset, config.tx_offset + config.tx_duration)
+}
+
+fn rx(config: &RadarConfig) -> (f64, f64) {
+    (config.rx_offset, config.rx_offset + config.rx_duration)
+}
+
+fn rx_full_resolution(config: &RadarConfig) -> (f64, f64) {
+    (config.rx_offset, config.rx_offset + config.full_resolution_rx_duration())
+}
+
+fn noise(config: &RadarConfig) -> (f64, f64) {
+    (config.rx_noise_offset, config.rx_noise_offset + config.rx_noise_duration)
+}
+
+fn reinj(config: &RadarConfig) -> (f64, f64) {
+    (config.rx_reinj_offset, config.rx_reinj_offset + config.rx_reinj_duration)
+}
+
+fn nadir(config: &RadarConfig) -> (f64, f64) {
+    (config.nadir_echo_time(), config.nadir_echo_time() + config.tx_duration)
+}
+
+fn pulses_later(shift: i64) -> String {
+    match shift {
+        0 => "in the same PRI".to_string(),
+        1 => "one PRI later".to_string(),
+        -1 => "one PRI earlier".to_string(),
+        k if k > 0 => format!("{} PRIs later", k),
+        k => format!("{} PRIs earlier", -k),
+    }
+}
+
+// Every timing constraint, checked in this order.
+pub const RULES: &[Rule] = &[
+    Rule { name: "pulse-in-pri", severity: Severity::Error, check: |config| {
+        let tx = tx(config);
+        let pri = config.raw_pri_sequence().into_iter().fold(f64::INFINITY, f64::min);
+        (tx.1 > pri).then(|| (
+            format!("the pulse ends at {:.3} µs, after the shortest PRI of {:.3} µs", tx.1, pri),
+            vec![TX],
+        ))
+    } },
+    Rule { name: "rx-overlaps-tx", severity: Severity::Error, check: |config| {
+        overlap_shift(rx(config), tx(config), &config.pri_sequence()).map(|k| (
+            format!("the RX window overlaps the pulse transmitted {}", pulses_later(k)),
+            vec![RX, TX],
+        ))
+    } },
+    Rule { name: "nadir-in-rx", severity: Severity::Warning, check: |config| {
+        overlap_shift(rx_full_resolution(config), nadir(config), &config.pri_sequence()).map(|k| (
+            format!("the nadir echo of the pulse transmitted {} falls inside the useful RX window", pulses_later(-k)),
+            vec![RX_FULL_RESOLUTION, NADIR],
+        ))
+    } },
+    Rule { name: "direct-path-in-rx", severity: Severity::Warning, check: |config| {
+        if !config.bsar_config {
+            return None;
+        }
+        let direct = (config.direct_path_time(), config.direct_path_time() + config.tx_duration);
+        overlap_shift(rx(config), direct, &config.pri_sequence()).map(|k| (
+            format!("the direct path signal of the pulse transmitted {} falls inside the RX window", pulses_later(-k)),
+            vec![RX, DIRECT_PATH],
+        ))
+    } },
+    Rule { name: "noise-overlaps-rx", severity: Severity::Warning, check: |config| {
+        overlaps_periodic(noise(config), rx(config), &config.pri_sequence()).then(|| (
+            "the noise window overlaps the RX window".to_string(),
+            vec![NOISE, RX],
+        ))
+    } },
+    Rule { name: "noise-overlaps-tx", severity: Severity::Warning, check: |config| {
+        overlaps_periodic(noise(config), tx(config), &config.pri_sequence()).then(|| (
+            "the noise window overlaps a transmitted pulse".to_string(),
+            vec![NOISE, TX],
+        ))
+    } },
+    Rule { name: "reinj-overlaps-rx", severity: Severity::Warning, check: |config| {
+        overlaps_periodic(reinj(config), rx(config), &config.pri_sequence()).then(|| (
+            "the reinjection window overlaps the RX window".to_string(),
+            vec![REINJ, RX],
+        ))
+    } },
+    Rule { name: "swath-in-rx", severity: Severity::Info, check: |config| {
+        let (first, last) = config.swath_echo_times();
+        let (start, end) = rx(config);
+        (first < start || last + config.tx_duration > end).then(|| (
+            format!(
+                "the RX window from {:.3} µs to {:.3} µs does not cover the swath echo from {:.3} µs to {:.3} µs",
+                start, end, first, last + config.tx_duration
+            ),
+            vec![RX, SWATH_ECHO],
+        ))
+    } },
+];
+
+// Violated rules for the timing programmed in the digitiser, the most severe first.
+pub fn violations(config: &RadarConfig) -> Vec<Violation> {
+    let config = &genio::programmed(config);
+    let mut violations: Vec<Violation> = RULES.iter()
+        .filter_map(|rule| {
+            (rule.check)(config).map(|(message, windows)| Violation {
+                rule: rule.name,
+                severity: rule.severity,
+                message,
+                windows,
+            })
+        })
+        .collect();
+    violations.sort_by_key(|v| std::cmp::Reverse(v.severity));
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pri::PriMode;
+
+    #[test]
+    fn periodic_overlap() {
+        assert!(overlaps_periodic((0.0, 10.0), (5.0, 15.0), &[100.0]));
+        assert!(!overlaps_periodic((0.0, 10.0), (10.0, 20.0), &[100.0]));
+        assert!(overlaps_periodic((95.0, 105.0), (0.0, 10.0), &[100.0]));
+        assert!(overlaps_periodic((0.0, 10.0), (195.0, 205.0), &[100.0]));
+        assert!(!overlaps_periodic((20.0, 90.0), (95.0, 110.0), &[100.0]));
+        assert_eq!(overlap_shift((95.0, 105.0), (0.0, 10.0), &[100.0]), Some(1));
+        assert_eq!(overlap_shift((0.0, 10.0), (195.0, 205.0), &[100.0]), Some(-2));
+
+        // Only the pulse following the shorter PRI of the sequence is met.
+        assert!(!overlaps_periodic((20.0, 65.0), (0.0, 10.0), &[100.0]));
+        assert_eq!(overlap_shift((20.0, 65.0), (0.0, 10.0), &[100.0, 60.0]), Some(1));
+        assert_eq!(overlap_shift((0.0, 10.0), (150.0, 170.0), &[100.0, 60.0]), Some(-2));
+    }
+
+    #[test]
+    fn rules() {
+        let config = RadarConfig::default();
+        let rules: Vec<_> = violations(&config).iter().map(|v| v.rule).collect();
+        assert_eq!(rules, ["nadir-in-rx"]);
+
+        let config = RadarConfig { rx_noise_offset: 40.0, rx_offset: 32.0, ..config };
+        let rules: Vec<_> = violations(&config).iter().map(|v| v.rule).collect();
+        assert_eq!(rules, ["noise-overlaps-rx", "swath-in-rx"]);
+
+        let config = RadarConfig { rx_duration: 90.0, ..config };
+        let violations = violations(&config);
+        assert_eq!(violations[0].rule, "rx-overlaps-tx");
+        assert_eq!(violations[0].severity, Severity::Error);
+        assert!(violations[0].message.contains("one PRI later"));
+    }
+
+    #[test]
+    fn staggered_rules() {
+        let config = RadarConfig { pri_mode: PriMode::List, pri_list: vec![100.0, 60.0], rx_duration: 40.0, ..RadarConfig::default() };
+        let violations = violations(&config);
+        let overlap = violations.iter().find(|v| v.rule == "rx-overlaps-tx").unwrap();
+        assert!(overlap.message.contains("one PRI later"));
+
+        let config = RadarConfig { pri_mode: PriMode::Constant, ..config };
+        assert!(super::violations(&config).iter().all(|v| v.rule != "rx-overlaps-tx"));
+    }
+
+    #[test]
+    fn pulse_longer_than_pri() {
+        let config = RadarConfig { pri: 5.0, tx_duration: 10.0, fech: 0.0, ..RadarConfig::default() };
+        let violations = violations(&config);
+        assert_eq!(violations[0].rule, "pulse-in-pri");
+        assert_eq!(violations[0].message, "the pulse ends at 10.000 µs, after the shortest PRI of 5.000 µs");
+        assert_eq!(config.mean_prf(), config.prf());
+
+        let config = RadarConfig { pri_mode: PriMode::List, pri_list: vec![100.0, 8.0], pri: 100.0, ..config };
+        assert_eq!(super::violations(&config)[0].rule, "pulse-in-pri");
+    }
+}
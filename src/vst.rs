@@ -0,0 +1,150 @@
+// VST files configure the bench vector signal transceiver generating the
+// transmitted pulse: `[Section]` headers and `Name_unit=value` lines.
+use std::fmt::Write;
+use crate::config::RadarConfig;
+use crate::import::{self, Imported, Mapping};
+
+const MAPPINGS: &[Mapping] = &[
+    Mapping { key: "Carrier.CenterFrequency_Hz", field: "sensitivity.center_frequency", scale: 1e-9 },
+    Mapping { key: "Carrier.NbAgilities", field: "tx.nb_agilities", scale: 1.0 },
+    Mapping { key: "Carrier.PeakPower_W", field: "sensitivity.peak_power", scale: 1.0 },
+    Mapping { key: "Pulse.Bandwidth_Hz", field: "sensitivity.bandwidth", scale: 1e-6 },
+    Mapping { key: "Pulse.Duration_s", field: "tx.duration", scale: 1e6 },
+    Mapping { key: "Pulse.PRI_s", field: "tx.pri", scale: 1e6 },
+];
+
+pub fn to_string(config: &RadarConfig) -> String {
+    let mut out = String::new();
+    writeln!(out, "; VST waveform generated by SARConf").unwrap();
+    writeln!(out, "; Configuration: {}", config.config_name).unwrap();
+
+    writeln!(out, "\n[Carrier]").unwrap();
+    writeln!(out, "CenterFrequency_Hz={}", (config.center_frequency * 1e9).round()).unwrap();
+    writeln!(out, "NbAgilities={}", config.nb_agilities).unwrap();
+    writeln!(out, "PeakPower_W={}", config.peak_power).unwrap();
+
+    writeln!(out, "\n[Agility]").unwrap();
+    for step in 0..config.nb_agilities {
+        writeln!(out, "Frequency{}_Hz={}", step, (config.agility_frequency(step) * 1e9).round()).unwrap();
+    }
+    let sequence: Vec<String> = config.agility_sequence().iter().map(u32::to_string).collect();
+    writeln!(out, "Sequence={}", sequence.join(",")).unwrap();
+
+    writeln!(out, "\n[Pulse]").unwrap();
+    writeln!(out, "Modulation=LinearChirp").unwrap();
+    writeln!(out, "Bandwidth_Hz={}", (config.bandwidth * 1e6).round()).unwrap();
+    writeln!(out, "Duration_s={}", config.tx_duration / 1e6).unwrap();
+    writeln!(out, "PRI_s={}", config.pri / 1e6).unwrap();
+    writeln!(out, "DutyCycle={}", config.tx_duration / config.pri).unwrap();
+    out
+}
+
+pub fn from_bytes(bytes: &[u8]) -> Imported {
+    let mut name = None;
+    let mut section = "";
+    let mut entries = Vec::new();
+    let mut frequencies = Vec::new();
+    let mut sequence: Option<Vec<u32>> = None;
+    let mut errors = Vec::new();
+    for (number, line) in import::text(bytes)?.lines().enumerate() {
+        if let Some(config_name) = line.strip_prefix("; Configuration: ") {
+            name = Some(config_name.to_string());
+        }
+        let line = line.split(';').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = header.trim();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            errors.push(format!("line {}: expected `Name=value`", number + 1));
+            continue;
+        };
+        let key = format!("{}.{}", section, key.trim());
+        match (key.as_str(), value.trim()) {
+            // Derived from the pulse duration and PRI, nothing to import.
+            ("Pulse.DutyCycle", _) => {}
+            ("Pulse.Modulation", "LinearChirp") => {}
+            ("Pulse.Modulation", other) => errors.push(format!("line {}: unsupported modulation \"{}\"", number + 1, other)),
+            ("Agility.Sequence", value) => match import::parse_sequence(value) {
+                Some(steps) => sequence = Some(steps),
+                None => errors.push(format!("line {}: invalid agility sequence \"{}\"", number + 1, value)),
+            },
+            (key, value) if key.starts_with("Agility.Frequency") => {
+                let step = key.strip_prefix("Agility.Frequency")
+                    .and_then(|key| key.strip_suffix("_Hz"))
+                    .and_then(|step| step.parse::<usize>().ok());
+                match (step, value.parse::<f64>()) {
+                    (Some(step), Ok(frequency)) => frequencies.push((step, frequency)),
+                    _ => errors.push(format!("line {}: invalid agility frequency \"{}={}\"", number + 1, key, value)),
+                }
+            }
+            (_, value) => match value.parse::<f64>() {
+                Ok(value) => entries.push((key, value)),
+                Err(_) => errors.push(format!("line {}: invalid number \"{}\"", number + 1, value)),
+            },
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let (mut config, mut warnings) = import::apply("VST", name, entries, MAPPINGS)?;
+
+    // Agility frequencies are stored as offsets from the center frequency.
+    let offsets = frequencies.into_iter()
+        .map(|(step, frequency)| {
+            let offset = (frequency - config.center_frequency * 1e9).round() / 1e6;
+            (format!("Agility.Frequency{}_Hz", step), step, offset)
+        })
+        .collect();
+    import::apply_agilities(&mut config, &mut warnings, offsets, sequence.map(|s| ("Agility.Sequence", s)))?;
+    Ok((config, warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut config = RadarConfig {
+            config_name: "Waveform".to_string(),
+            center_frequency: 9.6,
+            peak_power: 250.0,
+            bandwidth: 150.0,
+            tx_duration: 12.5,
+            pri: 125.0,
+            nb_agilities: 3,
+            ..RadarConfig::default()
+        };
+        config.resize_agilities();
+        config.agility_offsets = vec![0.0, 40.0, -40.0];
+        config.agility_sequence = vec![1, 2, 0];
+        let text = to_string(&config);
+        assert!(text.contains("Frequency2_Hz=9560000000\n"));
+        assert!(text.contains("DutyCycle=0.1\n"));
+
+        let (imported, _) = from_bytes(text.as_bytes()).unwrap();
+        assert_eq!(imported.config_name, "Waveform");
+        assert_eq!(imported.center_frequency, 9.6);
+        assert_eq!(imported.peak_power, 250.0);
+        assert_eq!(imported.bandwidth, 150.0);
+        assert_eq!((imported.tx_duration, imported.pri), (12.5, 125.0));
+        assert_eq!(imported.agility_offsets, config.agility_offsets);
+        assert_eq!(imported.agility_sequence, config.agility_sequence);
+    }
+
+    #[test]
+    fn unsupported_waveforms() {
+        let text = to_string(&RadarConfig { nb_agilities: 2, agility_offsets: vec![0.0; 2], ..RadarConfig::default() });
+        let errors = from_bytes(text.replace("LinearChirp", "Barker13").as_bytes()).err().unwrap();
+        assert_eq!(errors, ["line 15: unsupported modulation \"Barker13\""]);
+
+        let errors = from_bytes(text.replace("Sequence=0,1", "Sequence=0,x").as_bytes()).err().unwrap();
+        assert_eq!(errors, ["line 12: invalid agility sequence \"0,x\""]);
+        let errors = from_bytes(text.replace("Sequence=0,1", "Sequence=1,1").as_bytes()).err().unwrap();
+        assert_eq!(errors, ["Agility.Sequence: must list each of the 2 agility steps once"]);
+    }
+}